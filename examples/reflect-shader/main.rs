@@ -89,23 +89,48 @@ fn print_type(module: &Module, ty: &Type) {
     match ty {
         Type::Void => print!("void "),
         Type::Bool => print!("bool "),
-        Type::Int32 => print!("int "),
-        Type::UInt32 => print!("uint "),
-        Type::Float32 => print!("float "),
-        Type::Vec2 => print!("vec2 "),
-        Type::Vec3 => print!("vec3 "),
-        Type::Vec4 => print!("vec4 "),
-        Type::Mat3 => print!("mat3 "),
-        Type::Mat4 => print!("mat4 "),
-        Type::Image2D { .. } => print!("image2D "),
+        Type::Int {
+            width: 32,
+            signed: true,
+        } => print!("int "),
+        Type::Int {
+            width: 32,
+            signed: false,
+        } => print!("uint "),
+        Type::Int { width, signed } => {
+            print!("{}int{}_t ", if *signed { "" } else { "u" }, width)
+        }
+        Type::Float { width: 32 } => print!("float "),
+        Type::Float { width: 64 } => print!("double "),
+        Type::Float { width } => print!("float{}_t ", width),
+        Type::Vector {
+            component_type_id,
+            count,
+        } => {
+            print!("vec{}<", count);
+            print_type(module, module.get_type(*component_type_id).unwrap());
+            print!("> ");
+        }
+        Type::Matrix {
+            column_type_id,
+            columns,
+        } => {
+            print!("mat{}<", columns);
+            print_type(module, module.get_type(*column_type_id).unwrap());
+            print!("> ");
+        }
+        Type::Image { dim, arrayed, .. } => {
+            print!("image{:?}{} ", dim, if *arrayed { "Array" } else { "" })
+        }
         Type::Sampler => print!("sampler "),
         Type::SampledImage { .. } => print!("sampler2D "),
         Type::Array {
             element_type_id,
             length,
+            ..
         } => {
             print_type(module, module.get_type(*element_type_id).unwrap());
-            print!("[{}] ", length.unwrap_or(0));
+            print!("[{}] ", length.value().unwrap_or(0));
         }
         Type::Struct { name, elements } => {
             println!(
@@ -118,7 +143,7 @@ fn print_type(module: &Module, ty: &Type) {
                 if let Some(offset) = elem.offset {
                     print!("offset={}", offset);
                 }
-                if let Some(Type::Mat3 | Type::Mat4) = module.get_type(elem.type_id) {
+                if let Some(Type::Matrix { .. }) = module.get_type(elem.type_id) {
                     print!(
                         ", {}, stride={}",
                         if elem.row_major {
@@ -126,7 +151,7 @@ fn print_type(module: &Module, ty: &Type) {
                         } else {
                             "col_major"
                         },
-                        elem.stride
+                        elem.stride.unwrap_or(0)
                     );
                 }
                 print!(") ");
@@ -153,6 +178,8 @@ fn print_type(module: &Module, ty: &Type) {
             print_type(module, module.get_type(*pointed_type_id).unwrap());
             print!("* ");
         }
+        Type::AccelerationStructure => print!("accelerationStructure "),
+        Type::RayQuery => print!("rayQuery "),
         _ => print!("<unknown> "),
     }
 }
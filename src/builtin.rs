@@ -0,0 +1,115 @@
+spirv_enum!(
+    /// A built-in variable of a shader (e.g. GLSL `gl_Position`), declared by the `BuiltIn` decoration
+    BuiltIn {
+        0 = Position,
+        1 = PointSize,
+        3 = ClipDistance,
+        4 = CullDistance,
+        5 = VertexId,
+        6 = InstanceId,
+        7 = PrimitiveId,
+        8 = InvocationId,
+        9 = Layer,
+        10 = ViewportIndex,
+        11 = TessLevelOuter,
+        12 = TessLevelInner,
+        13 = TessCoord,
+        14 = PatchVertices,
+        15 = FragCoord,
+        16 = PointCoord,
+        17 = FrontFacing,
+        18 = SampleId,
+        19 = SamplePosition,
+        20 = SampleMask,
+        22 = FragDepth,
+        23 = HelperInvocation,
+        24 = NumWorkgroups,
+        25 = WorkgroupSize,
+        26 = WorkgroupId,
+        27 = LocalInvocationId,
+        28 = GlobalInvocationId,
+        29 = LocalInvocationIndex,
+        30 = WorkDim,
+        31 = GlobalSize,
+        32 = EnqueuedWorkgroupSize,
+        33 = GlobalOffset,
+        34 = GlobalLinearId,
+        36 = SubgroupSize,
+        37 = SubgroupMaxSize,
+        38 = NumSubgroups,
+        39 = NumEnqueuedSubgroups,
+        40 = SubgroupId,
+        41 = SubgroupLocalInvocationId,
+        42 = VertexIndex,
+        43 = InstanceIndex,
+        4160 = CoreIDARM,
+        4161 = CoreCountARM,
+        4162 = CoreMaxIDARM,
+        4163 = WarpIDARM,
+        4164 = WarpMaxIDARM,
+        4416 = SubgroupEqMask,
+        4417 = SubgroupGeMask,
+        4418 = SubgroupGtMask,
+        4419 = SubgroupLeMask,
+        4420 = SubgroupLtMask,
+        4424 = BaseVertex,
+        4425 = BaseInstance,
+        4426 = DrawIndex,
+        4432 = PrimitiveShadingRateKHR,
+        4438 = DeviceIndex,
+        4440 = ViewIndex,
+        4444 = ShadingRateKHR,
+        4992 = BaryCoordNoPerspAMD,
+        4993 = BaryCoordNoPerspCentroidAMD,
+        4994 = BaryCoordNoPerspSampleAMD,
+        4995 = BaryCoordSmoothAMD,
+        4996 = BaryCoordSmoothCentroidAMD,
+        4997 = BaryCoordSmoothSampleAMD,
+        4998 = BaryCoordPullModelAMD,
+        5014 = FragStencilRefEXT,
+        5253 = ViewportMaskNV,
+        5257 = SecondaryPositionNV,
+        5258 = SecondaryViewportMaskNV,
+        5261 = PositionPerViewNV,
+        5262 = ViewportMaskPerViewNV,
+        5264 = FullyCoveredEXT,
+        5274 = TaskCountNV,
+        5275 = PrimitiveCountNV,
+        5276 = PrimitiveIndicesNV,
+        5277 = ClipDistancePerViewNV,
+        5278 = CullDistancePerViewNV,
+        5279 = LayerPerViewNV,
+        5280 = MeshViewCountNV,
+        5281 = MeshViewIndicesNV,
+        5286 = BaryCoordKHR,
+        5287 = BaryCoordNoPerspKHR,
+        5292 = FragSizeEXT,
+        5293 = FragInvocationCountEXT,
+        5294 = PrimitivePointIndicesEXT,
+        5295 = PrimitiveLineIndicesEXT,
+        5296 = PrimitiveTriangleIndicesEXT,
+        5299 = CullPrimitiveEXT,
+        5319 = LaunchIdKHR,
+        5320 = LaunchSizeKHR,
+        5321 = WorldRayOriginKHR,
+        5322 = WorldRayDirectionKHR,
+        5323 = ObjectRayOriginKHR,
+        5324 = ObjectRayDirectionKHR,
+        5325 = RayTminKHR,
+        5326 = RayTmaxKHR,
+        5327 = InstanceCustomIndexKHR,
+        5330 = ObjectToWorldKHR,
+        5331 = WorldToObjectKHR,
+        5332 = HitTNV,
+        5333 = HitKindKHR,
+        5334 = CurrentRayTimeNV,
+        5335 = HitTriangleVertexPositionsKHR,
+        5337 = IncomingRayFlagsKHR,
+        5344 = RayGeometryIndexKHR,
+        5374 = WarpsPerSMNV,
+        5375 = SMCountNV,
+        5376 = WarpIDNV,
+        5377 = SMIDNV,
+        6021 = CullMaskKHR,
+    }
+);
@@ -0,0 +1,477 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{BuiltIn, Type};
+
+/// The value of a scalar constant
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConstantValue {
+    /// A boolean value
+    Bool(bool),
+    /// A signed integer value
+    Int(i64),
+    /// An unsigned integer value
+    UInt(u64),
+    /// A floating point value
+    Float(f64),
+}
+
+impl ConstantValue {
+    /// Decodes the literal `words` of a scalar constant of type `ty`
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn from_words(ty: &Type, words: &[u32]) -> Option<Self> {
+        // literals wider than 32 bits are stored with the low-order word first
+        let bits = match words {
+            [low] => u64::from(*low),
+            [low, high] => u64::from(*low) | u64::from(*high) << 32,
+            _ => return None,
+        };
+
+        match ty {
+            Type::Int { .. } => Self::from_bits(ty, bits),
+            Type::Float { width: 16 } => Some(Self::Float(half_to_f64(bits as u16))),
+            Type::Float { width: 32 } => Some(Self::Float(f64::from(f32::from_bits(bits as u32)))),
+            Type::Float { width: 64 } => Some(Self::Float(f64::from_bits(bits))),
+            _ => None,
+        }
+    }
+
+    /// Creates an integer or boolean value of type `ty` from the lower bits of `bits`
+    #[allow(clippy::cast_possible_wrap)]
+    fn from_bits(ty: &Type, bits: u64) -> Option<Self> {
+        match ty {
+            Type::Bool => Some(Self::Bool(bits != 0)),
+            Type::Int { width, signed } if (1..=64).contains(width) => {
+                // the unused upper bits are cleared for unsigned and sign extended for signed integers
+                let unused = 64 - width;
+                if *signed {
+                    Some(Self::Int((bits << unused) as i64 >> unused))
+                } else {
+                    Some(Self::UInt(bits << unused >> unused))
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the two's complement bit pattern of an integer or boolean value
+    #[allow(clippy::cast_sign_loss)]
+    fn to_bits(self) -> Option<u64> {
+        match self {
+            Self::Bool(value) => Some(u64::from(value)),
+            Self::Int(value) => Some(value as u64),
+            Self::UInt(value) => Some(value),
+            Self::Float(_) => None,
+        }
+    }
+
+    /// Converts the value to the representation used by constants of type `ty`
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    pub(crate) fn cast(self, ty: &Type) -> Option<Self> {
+        match (self, ty) {
+            (Self::Float(value), Type::Float { width: 64 }) => Some(Self::Float(value)),
+            (Self::Int(value), Type::Float { width: 64 }) => Some(Self::Float(value as f64)),
+            (Self::UInt(value), Type::Float { width: 64 }) => Some(Self::Float(value as f64)),
+            // 16-bit floats are only rounded to 32-bit precision
+            (Self::Float(value), Type::Float { .. }) => Some(Self::Float(f64::from(value as f32))),
+            (Self::Int(value), Type::Float { .. }) => Some(Self::Float(f64::from(value as f32))),
+            (Self::UInt(value), Type::Float { .. }) => Some(Self::Float(f64::from(value as f32))),
+            (Self::Float(_), _) => None,
+            _ => Self::from_bits(ty, self.to_bits()?),
+        }
+    }
+
+    /// Returns the value as an array length, if it is a non-negative integer
+    pub(crate) fn as_length(self) -> Option<u32> {
+        match self {
+            Self::Int(value) => u32::try_from(value).ok(),
+            Self::UInt(value) => u32::try_from(value).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// Describes a specialization constant declared in a SPIRV module
+#[derive(Debug, Clone)]
+pub struct SpecializationConstant {
+    /// The constant's id used for specialization (e.g. GLSL `layout(constant_id=XXX)`)
+    pub spec_id: u32,
+    /// The constant's name (if known)
+    pub name: Option<String>,
+    /// The type id of the constant's scalar [`Type`]
+    pub type_id: u32,
+    /// The value of the constant if it is not specialized
+    pub default: ConstantValue,
+}
+
+/// A size given by a constant, such as the length of an array or one dimension of a workgroup size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConstantSize {
+    /// The size is a fixed value (e.g. GLSL `float data[4]` or `layout(local_size_x=64) in;`)
+    Fixed(u32),
+    /// The size is given by a specialization constant (e.g. GLSL `float data[N]` with `layout(constant_id=0) const uint N`)
+    Specialization {
+        /// The `spec_id` of the [`SpecializationConstant`]
+        spec_id: u32,
+        /// The size if the constant is not specialized
+        default: u32,
+    },
+    /// The size is calculated from specialization constants (e.g. GLSL `float data[N * 2]`)
+    Expression {
+        /// The id of the `OpSpecConstantOp` holding the size
+        constant_id: u32,
+        /// The size if no constant is specialized, [`None`] if the expression cannot be evaluated
+        default: Option<u32>,
+    },
+}
+
+impl ConstantSize {
+    /// Returns the size if no constant is specialized, or [`None`] if it is not known
+    pub fn value(self) -> Option<u32> {
+        match self {
+            ConstantSize::Fixed(size) | ConstantSize::Specialization { default: size, .. } => {
+                Some(size)
+            }
+            ConstantSize::Expression { default, .. } => default,
+        }
+    }
+}
+
+/// A constant declared by `OpConstant*` or `OpSpecConstant*`
+#[derive(Debug, Clone)]
+pub(crate) struct RawConstant {
+    pub(crate) type_id: u32,
+    pub(crate) kind: ConstantKind,
+    /// true if the constant was declared by `OpSpecConstant*`
+    pub(crate) specialization: bool,
+    pub(crate) spec_id: Option<u32>,
+    /// The constant's `BuiltIn` decoration (if any)
+    pub(crate) builtin: Option<BuiltIn>,
+    pub(crate) name: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum ConstantKind {
+    /// A scalar literal
+    Scalar(ConstantValue),
+    /// A composite (vector, array, etc.) of other constants, declared by `Op*ConstantComposite`
+    Composite(Vec<u32>),
+    /// The result of an `OpSpecConstantOp` instruction
+    Operation { opcode: u32, operands: Vec<u32> },
+}
+
+/// The values of already evaluated constants, shared by all evaluations as long as no constant changes
+pub(crate) type ConstantCache = HashMap<u32, Option<ConstantValue>>;
+
+/// Calculates the current value of the scalar constant `id`.
+///
+/// Every constant is evaluated at most once, its value is stored in `cache`.
+/// Returns [`None`] if `id` is not a scalar constant or its value depends on unsupported operations or on itself.
+pub(crate) fn evaluate_constant(
+    constants: &HashMap<u32, RawConstant>,
+    types: &HashMap<u32, Type>,
+    id: u32,
+    cache: &mut ConstantCache,
+) -> Option<ConstantValue> {
+    // the operands are evaluated before the operations using them with an explicit stack,
+    // so long chains of operations cannot overflow the call stack
+    let mut stack = vec![id];
+    let mut visited = HashSet::new();
+
+    while let Some(&id) = stack.last() {
+        if cache.contains_key(&id) {
+            stack.pop();
+            continue;
+        }
+
+        let value = match constants.get(&id).map(|c| (c, &c.kind)) {
+            Some((_, ConstantKind::Scalar(value))) => Some(*value),
+            Some((constant, ConstantKind::Operation { opcode, operands })) => {
+                let pending: Vec<_> = operands
+                    .iter()
+                    .filter(|id| !cache.contains_key(id))
+                    .collect();
+                if !pending.is_empty() && visited.insert(id) {
+                    stack.extend(pending);
+                    continue;
+                }
+
+                // operands still pending on the second visit depend on this constant
+                if pending.is_empty() {
+                    operands
+                        .iter()
+                        .map(|id| {
+                            let value = cache.get(id).copied().flatten()?;
+                            let ty = types.get(&constants.get(id)?.type_id)?;
+                            Some((value, ty))
+                        })
+                        .collect::<Option<Vec<_>>>()
+                        .and_then(|operands| {
+                            evaluate_operation(types.get(&constant.type_id)?, *opcode, &operands)
+                        })
+                } else {
+                    None
+                }
+            }
+            Some((_, ConstantKind::Composite(_))) | None => None,
+        };
+
+        cache.insert(id, value);
+        stack.pop();
+    }
+
+    cache.get(&id).copied().flatten()
+}
+
+/// Determines the [`ConstantSize`] given by the constant `id`.
+///
+/// Returns [`None`] if `id` is not a constant, or a non-specialization constant that is not a valid size.
+pub(crate) fn resolve_size(
+    constants: &HashMap<u32, RawConstant>,
+    types: &HashMap<u32, Type>,
+    id: u32,
+    cache: &mut ConstantCache,
+) -> Option<ConstantSize> {
+    let constant = constants.get(&id)?;
+    let value = evaluate_constant(constants, types, id, cache).and_then(ConstantValue::as_length);
+
+    Some(
+        match (&constant.kind, constant.specialization, constant.spec_id) {
+            (ConstantKind::Scalar(_), false, _) => ConstantSize::Fixed(value?),
+            (ConstantKind::Scalar(_), true, Some(spec_id)) => ConstantSize::Specialization {
+                spec_id,
+                default: value?,
+            },
+            _ => ConstantSize::Expression {
+                constant_id: id,
+                default: value,
+            },
+        },
+    )
+}
+
+/// Re-evaluates `size` after specialization constants changed.
+///
+/// Returns [`None`] if `size` does not depend on specialization constants or can no longer be resolved.
+pub(crate) fn respecialize_size(
+    constants: &HashMap<u32, RawConstant>,
+    types: &HashMap<u32, Type>,
+    size: ConstantSize,
+    cache: &mut ConstantCache,
+) -> Option<ConstantSize> {
+    let id = match size {
+        ConstantSize::Fixed(_) => return None,
+        ConstantSize::Specialization { spec_id, .. } => constants
+            .iter()
+            .find(|(_, c)| c.specialization && c.spec_id == Some(spec_id))
+            .map(|(id, _)| *id)?,
+        ConstantSize::Expression { constant_id, .. } => constant_id,
+    };
+    resolve_size(constants, types, id, cache)
+}
+
+/// Evaluates the operations allowed in `OpSpecConstantOp` on scalar integers and booleans
+#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+fn evaluate_operation(
+    ty: &Type,
+    opcode: u32,
+    operands: &[(ConstantValue, &Type)],
+) -> Option<ConstantValue> {
+    // integer operands, interpreted as signed or unsigned values of their own width
+    let signed = |i: usize| -> Option<i64> {
+        let (value, ty) = operands.get(i)?;
+        Some(ConstantValue::from_bits(&signed_type(ty)?, value.to_bits()?)?.to_bits()? as i64)
+    };
+    let unsigned = |i: usize| -> Option<u64> {
+        let (value, ty) = operands.get(i)?;
+        ConstantValue::from_bits(&unsigned_type(ty)?, value.to_bits()?)?.to_bits()
+    };
+    let boolean = |i: usize| -> Option<bool> {
+        match operands.get(i)? {
+            (ConstantValue::Bool(value), _) => Some(*value),
+            _ => None,
+        }
+    };
+    let result = |bits: u64| ConstantValue::from_bits(ty, bits);
+    let compare = |value: bool| Some(ConstantValue::Bool(value));
+
+    match (opcode, operands.len()) {
+        // OpUConvert, OpSConvert
+        (113, 1) => result(unsigned(0)?),
+        (114, 1) => result(signed(0)? as u64),
+        // OpSNegate
+        (126, 1) => result(signed(0)?.wrapping_neg() as u64),
+        // OpIAdd, OpISub, OpIMul
+        (128, 2) => result(unsigned(0)?.wrapping_add(unsigned(1)?)),
+        (130, 2) => result(unsigned(0)?.wrapping_sub(unsigned(1)?)),
+        (132, 2) => result(unsigned(0)?.wrapping_mul(unsigned(1)?)),
+        // OpUDiv, OpSDiv, OpUMod, OpSRem, OpSMod
+        (134, 2) => result(unsigned(0)?.checked_div(unsigned(1)?)?),
+        (135, 2) => result(signed(0)?.checked_div(signed(1)?)? as u64),
+        (137, 2) => result(unsigned(0)?.checked_rem(unsigned(1)?)?),
+        (138, 2) => result(signed(0)?.checked_rem(signed(1)?)? as u64),
+        (139, 2) => {
+            let (a, b) = (signed(0)?, signed(1)?);
+            let rem = a.checked_rem(b)?;
+            // the result of OpSMod has the sign of the divisor
+            result(if rem != 0 && (rem < 0) != (b < 0) {
+                rem + b
+            } else {
+                rem
+            } as u64)
+        }
+        // OpLogicalEqual, OpLogicalNotEqual, OpLogicalOr, OpLogicalAnd, OpLogicalNot
+        (164, 2) => compare(boolean(0)? == boolean(1)?),
+        (165, 2) => compare(boolean(0)? != boolean(1)?),
+        (166, 2) => compare(boolean(0)? || boolean(1)?),
+        (167, 2) => compare(boolean(0)? && boolean(1)?),
+        (168, 1) => compare(!boolean(0)?),
+        // OpSelect
+        (169, 3) => Some(if boolean(0)? {
+            operands[1].0
+        } else {
+            operands[2].0
+        }),
+        // OpIEqual, OpINotEqual
+        (170, 2) => compare(unsigned(0)? == unsigned(1)?),
+        (171, 2) => compare(unsigned(0)? != unsigned(1)?),
+        // OpUGreaterThan, OpSGreaterThan, OpUGreaterThanEqual, OpSGreaterThanEqual
+        (172, 2) => compare(unsigned(0)? > unsigned(1)?),
+        (173, 2) => compare(signed(0)? > signed(1)?),
+        (174, 2) => compare(unsigned(0)? >= unsigned(1)?),
+        (175, 2) => compare(signed(0)? >= signed(1)?),
+        // OpULessThan, OpSLessThan, OpULessThanEqual, OpSLessThanEqual
+        (176, 2) => compare(unsigned(0)? < unsigned(1)?),
+        (177, 2) => compare(signed(0)? < signed(1)?),
+        (178, 2) => compare(unsigned(0)? <= unsigned(1)?),
+        (179, 2) => compare(signed(0)? <= signed(1)?),
+        // OpShiftRightLogical, OpShiftRightArithmetic, OpShiftLeftLogical
+        (194, 2) => result(unsigned(0)?.checked_shr(u32::try_from(unsigned(1)?).ok()?)?),
+        (195, 2) => result(signed(0)?.checked_shr(u32::try_from(unsigned(1)?).ok()?)? as u64),
+        (196, 2) => result(unsigned(0)?.checked_shl(u32::try_from(unsigned(1)?).ok()?)?),
+        // OpBitwiseOr, OpBitwiseXor, OpBitwiseAnd, OpNot
+        (197, 2) => result(unsigned(0)? | unsigned(1)?),
+        (198, 2) => result(unsigned(0)? ^ unsigned(1)?),
+        (199, 2) => result(unsigned(0)? & unsigned(1)?),
+        (200, 1) => result(!unsigned(0)?),
+        _ => None,
+    }
+}
+
+/// Returns the signed integer type with the same width as `ty`
+fn signed_type(ty: &Type) -> Option<Type> {
+    match ty {
+        Type::Int { width, .. } => Some(Type::Int {
+            width: *width,
+            signed: true,
+        }),
+        _ => None,
+    }
+}
+
+/// Returns the unsigned integer type with the same width as `ty`
+fn unsigned_type(ty: &Type) -> Option<Type> {
+    match ty {
+        Type::Int { width, .. } => Some(Type::Int {
+            width: *width,
+            signed: false,
+        }),
+        _ => None,
+    }
+}
+
+/// Converts the bits of an IEEE 754 half precision float
+fn half_to_f64(bits: u16) -> f64 {
+    let sign = if bits & 0x8000 == 0 { 1.0 } else { -1.0 };
+    let exponent = i32::from((bits >> 10) & 0x1f);
+    let mantissa = f64::from(bits & 0x3ff);
+
+    sign * match exponent {
+        0 => mantissa * 2f64.powi(-24),
+        0x1f if mantissa == 0.0 => f64::INFINITY,
+        0x1f => f64::NAN,
+        _ => (1.0 + mantissa / 1024.0) * 2f64.powi(exponent - 15),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_utils::Assembler, ArrayLength, ConstantSize, ConstantValue, Module, Type};
+
+    // declares `uint[length]`, where `length` is the constant `length_id`, as type 900
+    fn array_length(asm: &mut Assembler, length_id: u32) -> Option<ArrayLength> {
+        asm.op(22, &[800, 32]).op(28, &[900, 800, length_id]);
+        match Module::from_words(asm.words()).unwrap().get_type(900)? {
+            Type::Array { length, .. } => Some(*length),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn specialization_constants_are_listed_by_spec_id() {
+        let mut asm = Assembler::new();
+        asm.op_str(5, &[10], "COUNT", &[])
+            .op_str(5, &[11], "SCALE", &[])
+            .op(71, &[10, 1, 3])
+            .op(71, &[11, 1, 0])
+            .op(71, &[12, 1, 1])
+            .op(21, &[1, 32, 1])
+            .op(22, &[2, 32])
+            .op(20, &[3])
+            .op(50, &[1, 10, 0xffff_fffe])
+            .op(50, &[2, 11, 0x3fc0_0000])
+            .op(48, &[3, 12])
+            // neither a plain constant nor an operation can be specialized directly
+            .op(43, &[1, 13, 7])
+            .op(52, &[1, 14, 128, 10, 13]);
+        let module = Module::from_words(asm.words()).unwrap();
+
+        let constants: Vec<_> = module
+            .specialization_constants()
+            .iter()
+            .map(|c| (c.spec_id, c.name.as_deref(), c.type_id, c.default))
+            .collect();
+        assert_eq!(
+            constants,
+            [
+                (0, Some("SCALE"), 2, ConstantValue::Float(1.5)),
+                (1, None, 3, ConstantValue::Bool(true)),
+                (3, Some("COUNT"), 1, ConstantValue::Int(-2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn reused_operations_are_evaluated_once() {
+        let mut asm = Assembler::new();
+        asm.op(21, &[1, 32, 0]).op(43, &[1, 10, 1]);
+        // each operation adds the previous result to itself, doubling the value
+        for id in 11..43 {
+            asm.op(52, &[1, id, 128, id - 1, id - 1]);
+        }
+
+        assert_eq!(
+            array_length(&mut asm, 40),
+            Some(ArrayLength::Constant(ConstantSize::Expression {
+                constant_id: 40,
+                default: Some(1 << 30),
+            }))
+        );
+    }
+
+    #[test]
+    fn cyclic_operations_are_not_evaluated() {
+        let mut asm = Assembler::new();
+        asm.op(21, &[1, 32, 0])
+            .op(52, &[1, 10, 128, 11, 11])
+            .op(52, &[1, 11, 128, 10, 10]);
+
+        assert_eq!(
+            array_length(&mut asm, 10),
+            Some(ArrayLength::Constant(ConstantSize::Expression {
+                constant_id: 10,
+                default: None,
+            }))
+        );
+    }
+}
@@ -0,0 +1,276 @@
+use crate::ConstantSize;
+
+/// The local workgroup size of a compute, mesh or task shader
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkgroupSize {
+    /// The size in the x dimension
+    pub x: ConstantSize,
+    /// The size in the y dimension
+    pub y: ConstantSize,
+    /// The size in the z dimension
+    pub z: ConstantSize,
+}
+
+impl WorkgroupSize {
+    /// Returns the size in all three dimensions if no constant is specialized, or [`None`] if any of them is not known
+    pub fn value(self) -> Option<[u32; 3]> {
+        Some([self.x.value()?, self.y.value()?, self.z.value()?])
+    }
+}
+
+/// The execution modes of a fragment shader
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FragmentExecutionModes {
+    /// true if the shader may replace the depth of the fragment by writing `FragDepth`
+    pub depth_replacing: bool,
+    /// How a replaced depth relates to the interpolated depth (e.g. GLSL `layout(depth_greater) out float gl_FragDepth;`), if declared
+    pub depth_condition: Option<DepthCondition>,
+    /// true if the depth and stencil tests are performed before the shader runs (GLSL: `layout(early_fragment_tests) in;`)
+    pub early_fragment_tests: bool,
+    /// true if the origin of `FragCoord` is the upper left corner of the framebuffer (required by Vulkan)
+    pub origin_upper_left: bool,
+}
+
+/// Restricts the depth written by a fragment shader relative to the interpolated depth, see [`FragmentExecutionModes`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DepthCondition {
+    /// The written depth is greater than or equal to the interpolated depth (`DepthGreater`)
+    Greater,
+    /// The written depth is less than or equal to the interpolated depth (`DepthLess`)
+    Less,
+    /// The written depth equals the interpolated depth (`DepthUnchanged`)
+    Unchanged,
+}
+
+/// The execution modes of a geometry shader
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GeometryExecutionModes {
+    /// The type of primitive the shader is invoked for (e.g. GLSL `layout(triangles) in;`)
+    pub input_primitive: Option<GeometryInputPrimitive>,
+    /// The type of primitive emitted by the shader (e.g. GLSL `layout(triangle_strip) out;`)
+    pub output_primitive: Option<GeometryOutputPrimitive>,
+    /// The maximum number of vertices emitted by a single invocation (GLSL: `layout(max_vertices=XXX) out;`)
+    pub output_vertices: Option<u32>,
+    /// The number of invocations per input primitive (GLSL: `layout(invocations=XXX) in;`), [`None`] means a single invocation
+    pub invocations: Option<u32>,
+}
+
+/// The input primitive of a geometry shader, see [`GeometryExecutionModes`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GeometryInputPrimitive {
+    /// Single points (`InputPoints`)
+    Points,
+    /// Lines with 2 vertices (`InputLines`)
+    Lines,
+    /// Lines with adjacency, 4 vertices (`InputLinesAdjacency`)
+    LinesAdjacency,
+    /// Triangles with 3 vertices (`Triangles`)
+    Triangles,
+    /// Triangles with adjacency, 6 vertices (`InputTrianglesAdjacency`)
+    TrianglesAdjacency,
+}
+
+/// The output primitive of a geometry shader, see [`GeometryExecutionModes`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GeometryOutputPrimitive {
+    /// Single points (`OutputPoints`)
+    Points,
+    /// A strip of connected lines (`OutputLineStrip`)
+    LineStrip,
+    /// A strip of connected triangles (`OutputTriangleStrip`)
+    TriangleStrip,
+}
+
+/// The execution modes of a tessellation control or evaluation shader.
+///
+/// Except for `output_vertices` the modes may be declared in either stage, so the modes of both stages need to be combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TessellationExecutionModes {
+    /// The number of vertices in the output patch of the control shader (GLSL: `layout(vertices=XXX) out;`)
+    pub output_vertices: Option<u32>,
+    /// The domain the patch is subdivided in (e.g. GLSL `layout(quads) in;`)
+    pub primitive: Option<TessellationPrimitive>,
+    /// The spacing of the generated vertices along the edges (e.g. GLSL `layout(fractional_odd_spacing) in;`)
+    pub spacing: Option<TessellationSpacing>,
+    /// The winding order of the generated triangles (e.g. GLSL `layout(ccw) in;`)
+    pub vertex_order: Option<VertexOrder>,
+    /// true if the tessellator emits points instead of lines or triangles (GLSL: `layout(point_mode) in;`)
+    pub point_mode: bool,
+}
+
+/// The domain subdivided by the tessellator, see [`TessellationExecutionModes`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TessellationPrimitive {
+    /// A triangle, subdivided into triangles (`Triangles`)
+    Triangles,
+    /// A quad, subdivided into triangles (`Quads`)
+    Quads,
+    /// A set of lines (`Isolines`)
+    Isolines,
+}
+
+/// The spacing of the vertices generated by the tessellator, see [`TessellationExecutionModes`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TessellationSpacing {
+    /// Segments of equal size, the tessellation levels are rounded up to integers (`SpacingEqual`)
+    Equal,
+    /// The tessellation levels are rounded up to even integers (`SpacingFractionalEven`)
+    FractionalEven,
+    /// The tessellation levels are rounded up to odd integers (`SpacingFractionalOdd`)
+    FractionalOdd,
+}
+
+/// The winding order of the triangles generated by the tessellator, see [`TessellationExecutionModes`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VertexOrder {
+    /// Clockwise (`VertexOrderCw`)
+    Cw,
+    /// Counter-clockwise (`VertexOrderCcw`)
+    Ccw,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{
+        GeometryExecutionModes, GeometryInputPrimitive, GeometryOutputPrimitive,
+        TessellationExecutionModes, TessellationPrimitive, TessellationSpacing, VertexOrder,
+    };
+    use crate::{test_utils::Assembler, ConstantSize, ConstantValue, Module, WorkgroupSize};
+
+    // declares a shader with the execution model `model` and the function 100, followed by the instructions of `declare`
+    fn shader(model: u32, declare: impl FnOnce(&mut Assembler)) -> Module {
+        let mut asm = Assembler::new();
+        asm.entry_point(model, 100, &[]);
+        declare(&mut asm);
+        Module::from_words(asm.words()).unwrap()
+    }
+
+    fn size(module: &Module) -> WorkgroupSize {
+        module.get_entry_points()[0].workgroup_size.unwrap()
+    }
+
+    #[test]
+    fn local_size_is_fixed() {
+        let module = shader(5, |asm| {
+            asm.op(16, &[100, 17, 8, 4, 1]);
+        });
+        assert_eq!(size(&module).value(), Some([8, 4, 1]));
+    }
+
+    #[test]
+    fn local_size_id_resolves_constants() {
+        let module = shader(5, |asm| {
+            asm.op(331, &[100, 38, 10, 11, 12])
+                .op(71, &[10, 1, 0])
+                .op(21, &[1, 32, 0])
+                .op(50, &[1, 10, 16])
+                .op(43, &[1, 11, 2])
+                .op(43, &[1, 13, 15])
+                .op(52, &[1, 12, 130, 10, 13]);
+        });
+        assert_eq!(
+            size(&module),
+            WorkgroupSize {
+                x: ConstantSize::Specialization {
+                    spec_id: 0,
+                    default: 16,
+                },
+                y: ConstantSize::Fixed(2),
+                z: ConstantSize::Expression {
+                    constant_id: 12,
+                    default: Some(1),
+                },
+            }
+        );
+
+        let specialized = module.specialize(&HashMap::from([(0, ConstantValue::UInt(20))]));
+        assert_eq!(size(&specialized).value(), Some([20, 2, 5]));
+    }
+
+    #[test]
+    fn workgroup_size_builtin_overrides_local_size() {
+        let module = shader(5, |asm| {
+            asm.op(16, &[100, 17, 8, 8, 1])
+                .op(71, &[10, 1, 0])
+                .op(71, &[13, 11, 25])
+                .op(21, &[1, 32, 0])
+                .op(23, &[2, 1, 3])
+                .op(50, &[1, 10, 32])
+                .op(43, &[1, 11, 1])
+                .op(51, &[2, 13, 10, 11, 11]);
+        });
+        assert_eq!(
+            size(&module),
+            WorkgroupSize {
+                x: ConstantSize::Specialization {
+                    spec_id: 0,
+                    default: 32,
+                },
+                y: ConstantSize::Fixed(1),
+                z: ConstantSize::Fixed(1),
+            }
+        );
+
+        let specialized = module.specialize(&HashMap::from([(0, ConstantValue::UInt(64))]));
+        assert_eq!(size(&specialized).value(), Some([64, 1, 1]));
+    }
+
+    #[test]
+    fn geometry_modes_are_routed_to_geometry_shaders() {
+        let module = shader(3, |asm| {
+            asm.op(16, &[100, 22])
+                .op(16, &[100, 29])
+                .op(16, &[100, 26, 6])
+                .op(16, &[100, 0, 2]);
+        });
+        let entry_point = &module.get_entry_points()[0];
+
+        assert_eq!(
+            entry_point.geometry_modes,
+            Some(GeometryExecutionModes {
+                input_primitive: Some(GeometryInputPrimitive::Triangles),
+                output_primitive: Some(GeometryOutputPrimitive::TriangleStrip),
+                output_vertices: Some(6),
+                invocations: Some(2),
+            })
+        );
+        assert_eq!(entry_point.tessellation_modes, None);
+    }
+
+    #[test]
+    fn tessellation_modes_are_routed_to_tessellation_shaders() {
+        let module = shader(1, |asm| {
+            asm.op(16, &[100, 22])
+                .op(16, &[100, 26, 3])
+                .op(16, &[100, 3])
+                .op(16, &[100, 5])
+                .op(16, &[100, 10]);
+        });
+        let entry_point = &module.get_entry_points()[0];
+
+        assert_eq!(
+            entry_point.tessellation_modes,
+            Some(TessellationExecutionModes {
+                output_vertices: Some(3),
+                primitive: Some(TessellationPrimitive::Triangles),
+                spacing: Some(TessellationSpacing::FractionalOdd),
+                vertex_order: Some(VertexOrder::Ccw),
+                point_mode: true,
+            })
+        );
+        assert_eq!(entry_point.geometry_modes, None);
+
+        let module = shader(2, |asm| {
+            asm.op(16, &[100, 22]);
+        });
+        assert_eq!(
+            module.get_entry_points()[0].tessellation_modes,
+            Some(TessellationExecutionModes {
+                primitive: Some(TessellationPrimitive::Triangles),
+                ..Default::default()
+            })
+        );
+    }
+}
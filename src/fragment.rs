@@ -0,0 +1,195 @@
+use crate::{ArrayLength, BuiltIn, EntryPoint, ExecutionModel, Module, NumericType, Type};
+
+/// A single colour output of a fragment shader, written to the colour attachment at `location`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColorOutput {
+    /// The location of the output, selecting the colour attachment it is written to
+    pub location: u32,
+    /// The blend equation input of the output, 1 for the second source of dual-source blending
+    pub index: u32,
+    /// The type of the output's components, [`NumericType::Float`], [`NumericType::SInt`] or [`NumericType::UInt`]
+    pub component_type: NumericType,
+    /// The number of components written to the attachment (1 to 4)
+    pub component_count: u32,
+    /// The name of the output variable (if known)
+    pub name: Option<String>,
+}
+
+/// The outputs of a fragment shader, as returned by [`Module::fragment_outputs`]
+#[derive(Debug, Clone)]
+pub struct FragmentOutputs {
+    /// All colour outputs, in ascending order of their locations and indices
+    pub colors: Vec<ColorOutput>,
+    /// true if any output is the second source of dual-source blending (requires the Vulkan `dualSrcBlend` feature)
+    pub dual_source_blending: bool,
+    /// true if the shader writes the `FragDepth` built-in
+    pub writes_depth: bool,
+    /// true if the shader writes the `FragStencilRefEXT` built-in (requires `VK_EXT_shader_stencil_export`)
+    pub writes_stencil_ref: bool,
+}
+
+impl Module {
+    /// Collects the colour, depth and stencil outputs of the fragment shader `entry_point`.
+    ///
+    /// Arrays of outputs are expanded into one colour output per element.
+    /// Returns [`None`] if `entry_point` is not a fragment shader or the type of any output cannot be written to a colour attachment.
+    pub fn fragment_outputs(&self, entry_point: &EntryPoint) -> Option<FragmentOutputs> {
+        if entry_point.execution_model != ExecutionModel::Fragment {
+            return None;
+        }
+
+        let mut colors = Vec::new();
+        for output in &entry_point.outputs {
+            let index = output.index.unwrap_or(0);
+            let (length, type_id) = match self.get_type(output.type_id)? {
+                Type::Array {
+                    element_type_id,
+                    length,
+                    ..
+                } => match length {
+                    ArrayLength::Runtime => return None,
+                    length => (length.value()?, *element_type_id),
+                },
+                _ => (1, output.type_id),
+            };
+            let (component_type, component_count) = self.color_output_type(type_id)?;
+
+            for i in 0..length {
+                colors.push(ColorOutput {
+                    location: output.location + i,
+                    index,
+                    component_type,
+                    component_count,
+                    name: output.name.clone(),
+                });
+            }
+        }
+        colors.sort_by_key(|c| (c.location, c.index));
+
+        let writes = |builtin| {
+            entry_point
+                .builtin_outputs
+                .iter()
+                .any(|b| b.builtin == builtin)
+        };
+
+        Some(FragmentOutputs {
+            dual_source_blending: colors.iter().any(|c| c.index > 0),
+            colors,
+            writes_depth: writes(BuiltIn::FragDepth),
+            writes_stencil_ref: writes(BuiltIn::FragStencilRefEXT),
+        })
+    }
+
+    // Returns the component type and count of a scalar or vector colour output of type `type_id`
+    fn color_output_type(&self, type_id: u32) -> Option<(NumericType, u32)> {
+        let (component_type_id, count) = match self.get_type(type_id)? {
+            Type::Int { .. } | Type::Float { .. } => (type_id, 1),
+            Type::Vector {
+                component_type_id,
+                count,
+            } => (*component_type_id, *count),
+            _ => return None,
+        };
+
+        let component_type = match self.get_type(component_type_id)? {
+            Type::Int { signed: true, .. } => NumericType::SInt,
+            Type::Int { signed: false, .. } => NumericType::UInt,
+            Type::Float { .. } => NumericType::Float,
+            _ => return None,
+        };
+
+        Some((component_type, count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_utils::Assembler, ColorOutput, Module, NumericType};
+
+    // declares the output variables `vars` (with `decorate` applied) of a shader with the execution model `model`
+    fn shader_outputs(model: u32, vars: &[u32], decorate: &[&[u32]]) -> Module {
+        let mut asm = Assembler::new();
+        asm.entry_point(model, 100, vars);
+        for decoration in decorate {
+            asm.op(71, decoration);
+        }
+        // vec4 (10, 11), ivec2[2] (12) and float (13) outputs
+        asm.op(22, &[1, 32])
+            .op(23, &[2, 1, 4])
+            .op(32, &[3, 3, 2])
+            .op(21, &[4, 32, 1])
+            .op(23, &[5, 4, 2])
+            .op(21, &[6, 32, 0])
+            .op(43, &[6, 7, 2])
+            .op(28, &[8, 5, 7])
+            .op(32, &[9, 3, 8])
+            .op(32, &[14, 3, 1])
+            .op(59, &[3, 10, 3])
+            .op(59, &[3, 11, 3])
+            .op(59, &[9, 12, 3])
+            .op(59, &[14, 13, 3]);
+        Module::from_words(asm.words()).unwrap()
+    }
+
+    fn color(location: u32, index: u32, component_type: NumericType, count: u32) -> ColorOutput {
+        ColorOutput {
+            location,
+            index,
+            component_type,
+            component_count: count,
+            name: None,
+        }
+    }
+
+    #[test]
+    fn index_and_frag_depth_are_reported() {
+        let module = shader_outputs(
+            4,
+            &[10, 11, 12, 13],
+            &[
+                &[10, 30, 0],
+                &[11, 30, 0],
+                &[11, 32, 1],
+                &[12, 30, 1],
+                &[13, 11, 22],
+            ],
+        );
+        let outputs = module
+            .fragment_outputs(&module.get_entry_points()[0])
+            .unwrap();
+
+        assert_eq!(
+            outputs.colors,
+            [
+                color(0, 0, NumericType::Float, 4),
+                color(0, 1, NumericType::Float, 4),
+                color(1, 0, NumericType::SInt, 2),
+                color(2, 0, NumericType::SInt, 2),
+            ]
+        );
+        assert!(outputs.dual_source_blending);
+        assert!(outputs.writes_depth);
+        assert!(!outputs.writes_stencil_ref);
+    }
+
+    #[test]
+    fn plain_color_outputs_need_no_features() {
+        let module = shader_outputs(4, &[10], &[&[10, 30, 0]]);
+        let outputs = module
+            .fragment_outputs(&module.get_entry_points()[0])
+            .unwrap();
+
+        assert_eq!(outputs.colors, [color(0, 0, NumericType::Float, 4)]);
+        assert!(!outputs.dual_source_blending);
+        assert!(!outputs.writes_depth);
+    }
+
+    #[test]
+    fn other_stages_have_no_fragment_outputs() {
+        let module = shader_outputs(0, &[10], &[&[10, 30, 0]]);
+        assert!(module
+            .fragment_outputs(&module.get_entry_points()[0])
+            .is_none());
+    }
+}
@@ -0,0 +1,285 @@
+use crate::{Module, Type};
+
+spirv_enum!(
+    /// The dimensionality of a [`Type::Image`](crate::Type::Image)
+    ImageDim {
+        0 = D1,
+        1 = D2,
+        2 = D3,
+        3 = Cube,
+        4 = Rect,
+        5 = Buffer,
+        6 = SubpassData,
+        4173 = TileImageDataEXT,
+    }
+);
+
+spirv_enum!(
+    /// The access qualifier of a [`Type::Image`](crate::Type::Image) in an `OpenCL` kernel
+    AccessQualifier {
+        0 = ReadOnly,
+        1 = WriteOnly,
+        2 = ReadWrite,
+    }
+);
+
+/// Whether a [`Type::Image`](crate::Type::Image) is a depth image
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageDepth {
+    /// The image is not a depth image
+    NotDepth,
+    /// The image is a depth image (GLSL: `sampler2DShadow`, etc.)
+    Depth,
+    /// The module does not declare whether the image is a depth image
+    Unknown,
+}
+
+impl ImageDepth {
+    pub(crate) fn from_code(code: u32) -> Self {
+        match code {
+            0 => Self::NotDepth,
+            1 => Self::Depth,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// How a [`Type::Image`](crate::Type::Image) is accessed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageUsage {
+    /// The usage is only known at run time (only allowed in `OpenCL` kernels)
+    Runtime,
+    /// The image is used with a sampler (GLSL: `texture2D`, `samplerBuffer`, etc.)
+    Sampled,
+    /// The image is used without a sampler (GLSL: `image2D`, `imageBuffer`, `subpassInput`, etc.)
+    Storage,
+}
+
+impl ImageUsage {
+    pub(crate) fn from_code(code: u32) -> Self {
+        match code {
+            1 => Self::Sampled,
+            2 => Self::Storage,
+            _ => Self::Runtime,
+        }
+    }
+}
+
+spirv_enum!(
+    /// The texel format of a storage [`Type::Image`](crate::Type::Image) (e.g. GLSL `layout(rgba8)`)
+    ImageFormat {
+        1 = Rgba32f,
+        2 = Rgba16f,
+        3 = R32f,
+        4 = Rgba8,
+        5 = Rgba8Snorm,
+        6 = Rg32f,
+        7 = Rg16f,
+        8 = R11fG11fB10f,
+        9 = R16f,
+        10 = Rgba16,
+        11 = Rgb10A2,
+        12 = Rg16,
+        13 = Rg8,
+        14 = R16,
+        15 = R8,
+        16 = Rgba16Snorm,
+        17 = Rg16Snorm,
+        18 = Rg8Snorm,
+        19 = R16Snorm,
+        20 = R8Snorm,
+        21 = Rgba32i,
+        22 = Rgba16i,
+        23 = Rgba8i,
+        24 = R32i,
+        25 = Rg32i,
+        26 = Rg16i,
+        27 = Rg8i,
+        28 = R16i,
+        29 = R8i,
+        30 = Rgba32ui,
+        31 = Rgba16ui,
+        32 = Rgba8ui,
+        33 = R32ui,
+        34 = Rgb10a2ui,
+        35 = Rg32ui,
+        36 = Rg16ui,
+        37 = Rg8ui,
+        38 = R16ui,
+        39 = R8ui,
+        40 = R64ui,
+        41 = R64i,
+    }
+);
+
+/// How the components of an [`ImageFormat`] are interpreted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumericType {
+    /// Floating point values
+    Float,
+    /// Unsigned integers normalized to `[0, 1]`
+    UNorm,
+    /// Signed integers normalized to `[-1, 1]`
+    SNorm,
+    /// Unsigned integers
+    UInt,
+    /// Signed integers
+    SInt,
+}
+
+/// The `VkFormat` value, component count and [`NumericType`] of every known [`ImageFormat`]
+const FORMATS: [(ImageFormat, u32, u32, NumericType); 41] = [
+    (ImageFormat::Rgba32f, 109, 4, NumericType::Float),
+    (ImageFormat::Rgba16f, 97, 4, NumericType::Float),
+    (ImageFormat::R32f, 100, 1, NumericType::Float),
+    (ImageFormat::Rgba8, 37, 4, NumericType::UNorm),
+    (ImageFormat::Rgba8Snorm, 38, 4, NumericType::SNorm),
+    (ImageFormat::Rg32f, 103, 2, NumericType::Float),
+    (ImageFormat::Rg16f, 83, 2, NumericType::Float),
+    (ImageFormat::R11fG11fB10f, 122, 3, NumericType::Float),
+    (ImageFormat::R16f, 76, 1, NumericType::Float),
+    (ImageFormat::Rgba16, 91, 4, NumericType::UNorm),
+    (ImageFormat::Rgb10A2, 64, 4, NumericType::UNorm),
+    (ImageFormat::Rg16, 77, 2, NumericType::UNorm),
+    (ImageFormat::Rg8, 16, 2, NumericType::UNorm),
+    (ImageFormat::R16, 70, 1, NumericType::UNorm),
+    (ImageFormat::R8, 9, 1, NumericType::UNorm),
+    (ImageFormat::Rgba16Snorm, 92, 4, NumericType::SNorm),
+    (ImageFormat::Rg16Snorm, 78, 2, NumericType::SNorm),
+    (ImageFormat::Rg8Snorm, 17, 2, NumericType::SNorm),
+    (ImageFormat::R16Snorm, 71, 1, NumericType::SNorm),
+    (ImageFormat::R8Snorm, 10, 1, NumericType::SNorm),
+    (ImageFormat::Rgba32i, 108, 4, NumericType::SInt),
+    (ImageFormat::Rgba16i, 96, 4, NumericType::SInt),
+    (ImageFormat::Rgba8i, 42, 4, NumericType::SInt),
+    (ImageFormat::R32i, 99, 1, NumericType::SInt),
+    (ImageFormat::Rg32i, 102, 2, NumericType::SInt),
+    (ImageFormat::Rg16i, 82, 2, NumericType::SInt),
+    (ImageFormat::Rg8i, 21, 2, NumericType::SInt),
+    (ImageFormat::R16i, 75, 1, NumericType::SInt),
+    (ImageFormat::R8i, 14, 1, NumericType::SInt),
+    (ImageFormat::Rgba32ui, 107, 4, NumericType::UInt),
+    (ImageFormat::Rgba16ui, 95, 4, NumericType::UInt),
+    (ImageFormat::Rgba8ui, 41, 4, NumericType::UInt),
+    (ImageFormat::R32ui, 98, 1, NumericType::UInt),
+    (ImageFormat::Rgb10a2ui, 68, 4, NumericType::UInt),
+    (ImageFormat::Rg32ui, 101, 2, NumericType::UInt),
+    (ImageFormat::Rg16ui, 81, 2, NumericType::UInt),
+    (ImageFormat::Rg8ui, 20, 2, NumericType::UInt),
+    (ImageFormat::R16ui, 74, 1, NumericType::UInt),
+    (ImageFormat::R8ui, 13, 1, NumericType::UInt),
+    (ImageFormat::R64ui, 110, 1, NumericType::UInt),
+    (ImageFormat::R64i, 111, 1, NumericType::SInt),
+];
+
+impl ImageFormat {
+    /// Returns the format corresponding to the Vulkan `VkFormat` value `vk_format`, if there is one
+    pub fn from_vk_format(vk_format: u32) -> Option<Self> {
+        FORMATS
+            .iter()
+            .find(|(_, vk, _, _)| *vk == vk_format)
+            .map(|(format, _, _, _)| *format)
+    }
+
+    /// Returns the equivalent Vulkan `VkFormat` value (e.g. 37 = `VK_FORMAT_R8G8B8A8_UNORM` for [`ImageFormat::Rgba8`])
+    pub fn vk_format(self) -> Option<u32> {
+        self.properties().map(|(vk_format, _, _)| vk_format)
+    }
+
+    /// Returns the number of components of a texel
+    pub fn component_count(self) -> Option<u32> {
+        self.properties().map(|(_, components, _)| components)
+    }
+
+    /// Returns how the components of a texel are interpreted
+    pub fn numeric_type(self) -> Option<NumericType> {
+        self.properties().map(|(_, _, numeric_type)| numeric_type)
+    }
+
+    fn properties(self) -> Option<(u32, u32, NumericType)> {
+        FORMATS
+            .iter()
+            .find(|(format, _, _, _)| *format == self)
+            .map(|(_, vk_format, components, numeric_type)| {
+                (*vk_format, *components, *numeric_type)
+            })
+    }
+}
+
+impl Module {
+    /// Checks whether an image view with the Vulkan `VkFormat` value `vk_format` can be bound to the storage image `type_id`.
+    ///
+    /// If the image declares an [`ImageFormat`], the view must have exactly that format.
+    /// Otherwise, the numeric type of the view format must match the sampled type of the image.
+    /// Arrays of images are checked by their element type.
+    /// Returns false if `type_id` is not a storage image or `vk_format` is not a storage image format.
+    pub fn is_storage_image_format_compatible(&self, type_id: u32, vk_format: u32) -> bool {
+        let mut ty = self.get_type(type_id);
+        while let Some(Type::Array {
+            element_type_id, ..
+        }) = ty
+        {
+            ty = self.get_type(*element_type_id);
+        }
+
+        // subpass inputs are declared with the same `Sampled` operand as storage images
+        let Some(Type::Image {
+            sampled_type_id,
+            dim,
+            sampled: ImageUsage::Storage,
+            format,
+            ..
+        }) = ty
+        else {
+            return false;
+        };
+        if *dim == ImageDim::SubpassData {
+            return false;
+        }
+        let Some(view_format) = ImageFormat::from_vk_format(vk_format) else {
+            return false;
+        };
+        if let Some(format) = format {
+            return *format == view_format;
+        }
+
+        let wide = matches!(view_format, ImageFormat::R64ui | ImageFormat::R64i);
+        match (self.get_type(*sampled_type_id), view_format.numeric_type()) {
+            (
+                Some(Type::Float { .. }),
+                Some(NumericType::Float | NumericType::UNorm | NumericType::SNorm),
+            ) => true,
+            (Some(Type::Int { width, signed }), Some(NumericType::UInt | NumericType::SInt)) => {
+                *signed == (view_format.numeric_type() == Some(NumericType::SInt))
+                    && (*width == 64) == wide
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_utils::Assembler, Module};
+
+    // an image with the dimensionality `dim` and sampled type float, declared as a storage image (`Sampled = 2`) without a format
+    fn storage_image(dim: u32) -> Module {
+        let mut asm = Assembler::new();
+        asm.op(22, &[1, 32]).op(25, &[2, 1, dim, 0, 0, 0, 2, 0]);
+        Module::from_words(asm.words()).unwrap()
+    }
+
+    #[test]
+    fn storage_image_matches_numeric_type() {
+        let module = storage_image(1);
+        // VK_FORMAT_R32_SFLOAT, VK_FORMAT_R8G8B8A8_UNORM, VK_FORMAT_R32_UINT
+        assert!(module.is_storage_image_format_compatible(2, 100));
+        assert!(module.is_storage_image_format_compatible(2, 37));
+        assert!(!module.is_storage_image_format_compatible(2, 98));
+    }
+
+    #[test]
+    fn subpass_input_is_not_a_storage_image() {
+        assert!(!storage_image(6).is_storage_image_format_compatible(2, 100));
+    }
+}
@@ -0,0 +1,278 @@
+use crate::{ArrayLength, BuiltIn, EntryPoint, ExecutionModel, LocationVariable, Module, Type};
+
+/// A mismatch between the outputs of a shader stage and the inputs of the following stage, as returned by [`check_interface`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InterfaceMismatch {
+    /// An input is read from a location that no output is written to
+    MissingOutput {
+        /// The location of the input
+        location: u32,
+        /// The name of the input (if known)
+        name: Option<String>,
+    },
+    /// An input and an output at the same location have incompatible types
+    TypeMismatch {
+        /// The location of the input and output
+        location: u32,
+        /// The name of the output (if known)
+        output_name: Option<String>,
+        /// The name of the input (if known)
+        input_name: Option<String>,
+    },
+    /// An input and an output share a location, but start at different components
+    ComponentMismatch {
+        /// The location of the input and output
+        location: u32,
+        /// The first component of the output
+        output_component: u32,
+        /// The first component of the input
+        input_component: u32,
+    },
+    /// A member of the input built-in block (e.g. `gl_in[].gl_Position`) is not written by the output built-in block
+    MissingBuiltIn {
+        /// The built-in read by the input block
+        builtin: BuiltIn,
+    },
+    /// A built-in is declared at different member indices of the input and output built-in blocks
+    BuiltInBlockMismatch {
+        /// The built-in declared at different member indices
+        builtin: BuiltIn,
+    },
+}
+
+/// Checks that the outputs of `producer` (defined in `producer_module`) match the inputs of the following stage `consumer` (defined in `consumer_module`).
+///
+/// Every input location must be written by an output starting at the same location and component with a compatible type.
+/// Inputs and outputs spanning multiple locations (matrices, arrays, structs and 64-bit vectors) are compared as a whole.
+/// The per-vertex arrays of tessellation, geometry and mesh shaders are matched by their element types,
+/// per-patch variables of tessellation shaders are matched as a whole.
+/// Built-ins read from an input block (e.g. `gl_in[].gl_Position`) must be members of the producer's output block.
+///
+/// Returns all mismatches found, an empty list means the interfaces match.
+pub fn check_interface(
+    producer_module: &Module,
+    producer: &EntryPoint,
+    consumer_module: &Module,
+    consumer: &EntryPoint,
+) -> Vec<InterfaceMismatch> {
+    let mut mismatches = Vec::new();
+
+    // all outputs with their type ids, without the per-vertex array
+    let outputs: Vec<_> = producer
+        .outputs
+        .iter()
+        .map(|o| {
+            let type_id = per_vertex_type(
+                producer_module,
+                o.type_id,
+                !o.patch
+                    && matches!(
+                        producer.execution_model,
+                        ExecutionModel::TessellationControl
+                            | ExecutionModel::MeshNV
+                            | ExecutionModel::MeshEXT
+                    ),
+            );
+            (o, type_id)
+        })
+        .collect();
+
+    for input in &consumer.inputs {
+        let input_type_id = per_vertex_type(
+            consumer_module,
+            input.type_id,
+            !input.patch
+                && matches!(
+                    consumer.execution_model,
+                    ExecutionModel::TessellationControl
+                        | ExecutionModel::TessellationEvaluation
+                        | ExecutionModel::Geometry
+                ),
+        );
+        let input_component = input.component.unwrap_or(0);
+
+        if let Some((output, output_type_id)) = outputs.iter().find(|(o, _)| {
+            o.location == input.location && o.component.unwrap_or(0) == input_component
+        }) {
+            if !producer_module.is_type_compatible(*output_type_id, consumer_module, input_type_id)
+            {
+                mismatches.push(InterfaceMismatch::TypeMismatch {
+                    location: input.location,
+                    output_name: output.name.clone(),
+                    input_name: input.name.clone(),
+                });
+            }
+        } else if let Some((output, _)) = outputs.iter().find(|(o, _)| o.location == input.location)
+        {
+            mismatches.push(InterfaceMismatch::ComponentMismatch {
+                location: input.location,
+                output_component: output.component.unwrap_or(0),
+                input_component,
+            });
+        } else if outputs
+            .iter()
+            .any(|(o, type_id)| covers(producer_module, o, *type_id, input.location))
+        {
+            // the location is written as part of a larger output, e.g. a matrix column
+            mismatches.push(InterfaceMismatch::TypeMismatch {
+                location: input.location,
+                output_name: None,
+                input_name: input.name.clone(),
+            });
+        } else {
+            mismatches.push(InterfaceMismatch::MissingOutput {
+                location: input.location,
+                name: input.name.clone(),
+            });
+        }
+    }
+
+    for input in consumer
+        .builtin_inputs
+        .iter()
+        .filter(|b| b.member.is_some())
+    {
+        match producer
+            .builtin_outputs
+            .iter()
+            .find(|b| b.builtin == input.builtin)
+        {
+            None => mismatches.push(InterfaceMismatch::MissingBuiltIn {
+                builtin: input.builtin,
+            }),
+            Some(output) if output.member.is_some() && output.member != input.member => {
+                mismatches.push(InterfaceMismatch::BuiltInBlockMismatch {
+                    builtin: input.builtin,
+                });
+            }
+            Some(_) => {}
+        }
+    }
+
+    mismatches
+}
+
+/// Returns the element type of the per-vertex array `type_id` if `arrayed` is set, otherwise `type_id` itself
+fn per_vertex_type(module: &Module, type_id: u32, arrayed: bool) -> u32 {
+    match module.get_type(type_id) {
+        Some(Type::Array {
+            element_type_id, ..
+        }) if arrayed => *element_type_id,
+        _ => type_id,
+    }
+}
+
+/// Returns true if the variable `var` of type `type_id` occupies `location`
+fn covers(module: &Module, var: &LocationVariable, type_id: u32, location: u32) -> bool {
+    module
+        .location_count(type_id)
+        .and_then(|count| var.location.checked_add(count))
+        .is_some_and(|end| (var.location..end).contains(&location))
+}
+
+impl Module {
+    /// Counts the number of locations taken by a value of type `type_id` in the shader interface.
+    ///
+    /// Returns [`None`] for types without a location or if the count does not fit into a `u32`.
+    pub(crate) fn location_count(&self, type_id: u32) -> Option<u32> {
+        match self.get_type(type_id)? {
+            Type::Bool | Type::Int { .. } | Type::Float { .. } => Some(1),
+            Type::Vector {
+                component_type_id,
+                count,
+            } => match self.get_type(*component_type_id)? {
+                // 64-bit vectors with more than 2 components take two locations
+                Type::Int { width: 64, .. } | Type::Float { width: 64 } if *count > 2 => Some(2),
+                _ => Some(1),
+            },
+            Type::Matrix {
+                column_type_id,
+                columns,
+            } => columns.checked_mul(self.location_count(*column_type_id)?),
+            Type::Array {
+                element_type_id,
+                length,
+                ..
+            } => match length {
+                ArrayLength::Runtime => None,
+                length => length
+                    .value()?
+                    .checked_mul(self.location_count(*element_type_id)?),
+            },
+            Type::Struct { elements, .. } => elements.iter().try_fold(0u32, |count, e| {
+                count.checked_add(self.location_count(e.type_id)?)
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_interface, InterfaceMismatch};
+    use crate::{test_utils::Assembler, Module};
+
+    // a vertex shader writing `mat4 out[length]` at location 0 and a fragment shader reading `vec4` at location 2
+    fn stages(length: u32) -> (Module, Module) {
+        let mut producer = Assembler::new();
+        producer
+            .entry_point(0, 100, &[20])
+            .op(71, &[20, 30, 0])
+            .op(22, &[1, 32])
+            .op(23, &[2, 1, 4])
+            .op(24, &[3, 2, 4])
+            .op(21, &[4, 32, 0])
+            .op(43, &[4, 5, length])
+            .op(28, &[6, 3, 5])
+            .op(32, &[7, 3, 6])
+            .op(59, &[7, 20, 3]);
+
+        let mut consumer = Assembler::new();
+        consumer
+            .entry_point(4, 100, &[20])
+            .op(71, &[20, 30, 2])
+            .op(22, &[1, 32])
+            .op(23, &[2, 1, 4])
+            .op(32, &[7, 1, 2])
+            .op(59, &[7, 20, 1]);
+
+        (
+            Module::from_words(producer.words()).unwrap(),
+            Module::from_words(consumer.words()).unwrap(),
+        )
+    }
+
+    fn mismatches(length: u32) -> Vec<InterfaceMismatch> {
+        let (producer, consumer) = stages(length);
+        check_interface(
+            &producer,
+            &producer.get_entry_points()[0],
+            &consumer,
+            &consumer.get_entry_points()[0],
+        )
+    }
+
+    #[test]
+    fn input_inside_larger_output_is_a_type_mismatch() {
+        assert_eq!(
+            mismatches(1),
+            [InterfaceMismatch::TypeMismatch {
+                location: 2,
+                output_name: None,
+                input_name: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn overflowing_location_count_does_not_cover() {
+        assert_eq!(
+            mismatches(1 << 30),
+            [InterfaceMismatch::MissingOutput {
+                location: 2,
+                name: None,
+            }]
+        );
+    }
+}
@@ -0,0 +1,532 @@
+use crate::{ArrayLength, EntryPoint, Module, StorageClass, StructMember, Type};
+
+/// Describes the memory layout of a single value of a [`Type`] inside a block (Uniform Buffer, Push Constants, etc.)
+#[derive(Debug, Clone)]
+pub struct TypeLayout {
+    /// The type id of the value's [`Type`]
+    pub type_id: u32,
+    /// The offset of the value in bytes, relative to the start of the outermost type
+    pub offset: u32,
+    /// The size of the value in bytes, without trailing padding
+    pub size: u32,
+    /// The base alignment of the value in bytes, according to the std430 rules
+    pub alignment: u32,
+    /// Describes how the contents of the value are laid out
+    pub kind: LayoutKind,
+}
+
+/// The type specific part of a [`TypeLayout`]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum LayoutKind {
+    /// A single scalar
+    Scalar,
+    /// A 64-bit device address pointing into a buffer (GLSL: `buffer_reference`)
+    Pointer,
+    /// A vector of scalars, tightly packed
+    Vector {
+        /// The number of components of the vector
+        components: u32,
+    },
+    /// A matrix, stored as an array of column or row vectors
+    Matrix {
+        /// The number of columns of the matrix
+        columns: u32,
+        /// The number of rows of the matrix
+        rows: u32,
+        /// The stride in bytes between two columns (or rows if `row_major` is true)
+        stride: u32,
+        /// true if the matrix is stored in row major order
+        row_major: bool,
+    },
+    /// An array of values
+    Array {
+        /// The layout of the first element, the following elements are placed `stride` bytes apart
+        element: Box<TypeLayout>,
+        /// The stride in bytes between two elements
+        stride: u32,
+        /// The number of elements, [`None`] for runtime arrays (which have a `size` of 0)
+        length: Option<u32>,
+    },
+    /// A struct containing other values
+    Struct {
+        /// The layouts of the struct members, in declaration order
+        members: Vec<MemberLayout>,
+        /// The number of padding bytes following the last member.
+        ///
+        /// For array elements, this is the space up to the next element, otherwise the space up to the struct's alignment.
+        padding: u32,
+    },
+}
+
+/// The layout of a single member of a [`LayoutKind::Struct`]
+#[derive(Debug, Clone)]
+pub struct MemberLayout {
+    /// The name of the member variable (if known)
+    pub name: Option<String>,
+    /// The layout of the member
+    pub layout: TypeLayout,
+}
+
+/// A single leaf field (scalar, vector or matrix) of a block, as returned by [`Module::field_offset`] and [`Module::flatten_block`]
+#[derive(Debug, Clone)]
+pub struct BlockField {
+    /// The fully qualified name of the field (e.g. `lights[2].color`)
+    pub name: String,
+    /// The offset of the field in bytes, relative to the start of the block
+    pub offset: u32,
+    /// The type id of the field's [`Type`]
+    pub type_id: u32,
+    /// The size of the field in bytes
+    pub size: u32,
+}
+
+impl Module {
+    /// Computes the full memory layout of the type indicated by `type_id`.
+    ///
+    /// If the type is used as a struct member, `member` supplies its offset as well as its matrix stride and majorness.
+    /// Returns [`None`] if `type_id` is not a type, the layout of any contained type is unknown or any offset or size
+    /// does not fit into a `u32`.
+    pub fn layout(&self, type_id: u32, member: Option<&StructMember>) -> Option<TypeLayout> {
+        self.layout_at(
+            type_id,
+            member,
+            member.and_then(|member| member.offset).unwrap_or(0),
+        )
+    }
+
+    /// Looks up a field of the block type `type_id` by its `path` (e.g. `lights[2].color`).
+    ///
+    /// Members without a name can be addressed by their index. The path does not need to end at a leaf field,
+    /// so e.g. `lights[2]` returns the offset and size of the whole array element.
+    /// Returns [`None`] if the path does not exist, the offset does not fit into a `u32` or the layout of the block is unknown.
+    pub fn field_offset(&self, type_id: u32, path: &str) -> Option<BlockField> {
+        let mut layout = &self.layout(type_id, None)?;
+        // byte offset of the current array element, since the layout only contains offsets of the first elements
+        let mut element_offset = 0;
+
+        for segment in path.split('.') {
+            let (name, mut indices) = segment.split_once('[').unwrap_or((segment, ""));
+
+            if !name.is_empty() {
+                let LayoutKind::Struct { members, .. } = &layout.kind else {
+                    return None;
+                };
+                let index = members.iter().position(|m| m.name.as_deref() == Some(name));
+                let index = index.or_else(|| name.parse().ok())?;
+                layout = &members.get(index)?.layout;
+            }
+
+            while !indices.is_empty() {
+                let (index, rest) = indices.split_once(']')?;
+                let index: u32 = index.parse().ok()?;
+                let LayoutKind::Array {
+                    element,
+                    stride,
+                    length,
+                } = &layout.kind
+                else {
+                    return None;
+                };
+                if length.is_some_and(|length| index >= length) {
+                    return None;
+                }
+
+                element_offset = index
+                    .checked_mul(*stride)
+                    .and_then(|offset| offset.checked_add(element_offset))?;
+                layout = element;
+                indices = rest.strip_prefix('[').or(rest.is_empty().then_some(rest))?;
+            }
+        }
+
+        Some(BlockField {
+            name: path.to_owned(),
+            offset: layout.offset.checked_add(element_offset)?,
+            type_id: layout.type_id,
+            size: layout.size,
+        })
+    }
+
+    /// Lists every leaf field (scalar, vector or matrix) of the block type `type_id` with its fully qualified name and offset.
+    ///
+    /// Arrays are expanded into one entry per element, except for runtime arrays, which only list their first element.
+    /// Members without a name are named by their index.
+    /// Returns [`None`] if the layout of the block is unknown or the offset of any field does not fit into a `u32`.
+    pub fn flatten_block(&self, type_id: u32) -> Option<Vec<BlockField>> {
+        let layout = self.layout(type_id, None)?;
+
+        let mut fields = Vec::new();
+        Self::flatten_layout(&layout, String::new(), 0, &mut fields)?;
+        Some(fields)
+    }
+
+    /// Computes the layout of the shader record buffer (GLSL: `shaderRecordEXT`) used by `entry_point`.
+    ///
+    /// Returns [`None`] if the entry point does not use a shader record buffer or its layout is unknown.
+    pub fn shader_record_layout(&self, entry_point: &EntryPoint) -> Option<TypeLayout> {
+        let var = entry_point
+            .ray_tracing_variables
+            .iter()
+            .find(|v| v.storage_class == StorageClass::ShaderRecordBuffer)?;
+        self.layout(var.type_id, None)
+    }
+
+    fn flatten_layout(
+        layout: &TypeLayout,
+        name: String,
+        element_offset: u32,
+        fields: &mut Vec<BlockField>,
+    ) -> Option<()> {
+        match &layout.kind {
+            LayoutKind::Struct { members, .. } => {
+                for (i, member) in members.iter().enumerate() {
+                    let member_name = member.name.clone().unwrap_or_else(|| i.to_string());
+                    let member_name = if name.is_empty() {
+                        member_name
+                    } else {
+                        format!("{name}.{member_name}")
+                    };
+                    Self::flatten_layout(&member.layout, member_name, element_offset, fields)?;
+                }
+            }
+            LayoutKind::Array {
+                element,
+                stride,
+                length,
+            } => {
+                for i in 0..length.unwrap_or(1) {
+                    Self::flatten_layout(
+                        element,
+                        format!("{name}[{i}]"),
+                        i.checked_mul(*stride)?.checked_add(element_offset)?,
+                        fields,
+                    )?;
+                }
+            }
+            _ => fields.push(BlockField {
+                name,
+                offset: layout.offset.checked_add(element_offset)?,
+                type_id: layout.type_id,
+                size: layout.size,
+            }),
+        }
+
+        Some(())
+    }
+
+    fn layout_at(
+        &self,
+        type_id: u32,
+        member: Option<&StructMember>,
+        offset: u32,
+    ) -> Option<TypeLayout> {
+        let (size, alignment, kind) = match self.get_type(type_id)? {
+            Type::Bool => (4, 4, LayoutKind::Scalar),
+            Type::Int { width, .. } | Type::Float { width } => {
+                (width / 8, width / 8, LayoutKind::Scalar)
+            }
+            Type::Pointer {
+                storage_class: StorageClass::PhysicalStorageBuffer,
+                ..
+            } => (8, 8, LayoutKind::Pointer),
+            Type::Vector {
+                component_type_id,
+                count,
+            } => {
+                let component = self.layout_at(*component_type_id, None, offset)?;
+                // 3 component vectors are aligned like 4 component vectors
+                let alignment = if *count == 2 { 2 } else { 4 } * component.alignment;
+
+                (
+                    count * component.size,
+                    alignment,
+                    LayoutKind::Vector { components: *count },
+                )
+            }
+            Type::Matrix {
+                column_type_id,
+                columns,
+            } => {
+                let Type::Vector {
+                    component_type_id,
+                    count: rows,
+                } = self.get_type(*column_type_id)?
+                else {
+                    return None;
+                };
+                let component = self.layout_at(*component_type_id, None, offset)?;
+                Self::matrix_layout(*columns, *rows, component.size, member)?
+            }
+            Type::Array {
+                element_type_id,
+                length,
+                stride,
+            } => {
+                let length = match length {
+                    ArrayLength::Runtime => None,
+                    length => Some(length.value()?),
+                };
+                let mut element = self.layout_at(*element_type_id, member, offset)?;
+                let stride = match stride {
+                    Some(stride) => *stride,
+                    None => round_up(element.size, element.alignment)?,
+                };
+                if let LayoutKind::Struct { padding, .. } = &mut element.kind {
+                    *padding = stride.saturating_sub(element.size);
+                }
+
+                (
+                    length.map_or(Some(0), |length| length.checked_mul(stride))?,
+                    element.alignment,
+                    LayoutKind::Array {
+                        element: Box::new(element),
+                        stride,
+                        length,
+                    },
+                )
+            }
+            Type::Struct { elements, .. } => {
+                let members = elements
+                    .iter()
+                    .map(|e| {
+                        Some(MemberLayout {
+                            name: e.name.clone(),
+                            layout: self.layout_at(
+                                e.type_id,
+                                Some(e),
+                                offset.checked_add(e.offset?)?,
+                            )?,
+                        })
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+
+                // Since there is no Size Decoration in SPIRV that tells us the size,
+                // we calculate it from the member that ends last.
+                let size = members
+                    .iter()
+                    .map(|m| (m.layout.offset - offset).checked_add(m.layout.size))
+                    .collect::<Option<Vec<_>>>()?
+                    .into_iter()
+                    .max()
+                    .unwrap_or(0);
+                let alignment = members
+                    .iter()
+                    .map(|m| m.layout.alignment)
+                    .max()
+                    .unwrap_or(1);
+
+                (
+                    size,
+                    alignment,
+                    LayoutKind::Struct {
+                        members,
+                        padding: round_up(size, alignment)? - size,
+                    },
+                )
+            }
+            _ => return None,
+        };
+
+        Some(TypeLayout {
+            type_id,
+            offset,
+            size,
+            alignment,
+            kind,
+        })
+    }
+
+    fn matrix_layout(
+        columns: u32,
+        rows: u32,
+        component_size: u32,
+        member: Option<&StructMember>,
+    ) -> Option<(u32, u32, LayoutKind)> {
+        let row_major = member.is_some_and(|m| m.row_major);
+
+        // a matrix is stored as an array of column vectors (or row vectors if row_major is set)
+        let (vectors, components) = if row_major {
+            (rows, columns)
+        } else {
+            (columns, rows)
+        };
+        let alignment = if components == 2 { 2 } else { 4 } * component_size;
+        // without a MatrixStride decoration, the vectors are tightly packed like array elements
+        let stride = member.and_then(|m| m.stride).unwrap_or(alignment);
+
+        Some((
+            stride
+                .checked_mul(vectors.checked_sub(1)?)?
+                .checked_add(components * component_size)?,
+            alignment,
+            LayoutKind::Matrix {
+                columns,
+                rows,
+                stride,
+                row_major,
+            },
+        ))
+    }
+}
+
+fn round_up(value: u32, alignment: u32) -> Option<u32> {
+    value.checked_next_multiple_of(alignment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LayoutKind, TypeLayout};
+    use crate::{test_utils::Assembler, Module};
+
+    // returns the (offset, size) of every member of a struct layout
+    fn members(layout: &TypeLayout) -> Vec<(u32, u32)> {
+        match &layout.kind {
+            LayoutKind::Struct { members, .. } => members
+                .iter()
+                .map(|m| (m.layout.offset, m.layout.size))
+                .collect(),
+            _ => panic!("not a struct: {layout:?}"),
+        }
+    }
+
+    // `struct { float a; vec3 b; layout(row_major) mat4x3 m; mat4x3 c; float arr[3]; }` with std140 offsets as type 8
+    fn std140_block() -> Module {
+        let mut asm = Assembler::new();
+        asm.op(71, &[7, 6, 16])
+            .op(72, &[8, 0, 35, 0])
+            .op(72, &[8, 1, 35, 16])
+            .op(72, &[8, 2, 35, 32])
+            .op(72, &[8, 2, 4])
+            .op(72, &[8, 2, 7, 16])
+            .op(72, &[8, 3, 35, 80])
+            .op(72, &[8, 3, 5])
+            .op(72, &[8, 3, 7, 16])
+            .op(72, &[8, 4, 35, 144])
+            .op(22, &[1, 32])
+            .op(23, &[2, 1, 3])
+            .op(24, &[4, 2, 4])
+            .op(21, &[5, 32, 0])
+            .op(43, &[5, 6, 3])
+            .op(28, &[7, 1, 6])
+            .op(30, &[8, 1, 2, 4, 4, 7]);
+        Module::from_words(asm.words()).unwrap()
+    }
+
+    #[test]
+    fn member_offsets_and_sizes() {
+        let layout = std140_block().layout(8, None).unwrap();
+        assert_eq!(
+            members(&layout),
+            [(0, 4), (16, 12), (32, 48), (80, 60), (144, 48)]
+        );
+        assert_eq!((layout.size, layout.alignment), (192, 16));
+    }
+
+    #[test]
+    fn matrix_majorness() {
+        let layout = std140_block().layout(8, None).unwrap();
+        let LayoutKind::Struct { members, .. } = &layout.kind else {
+            panic!("not a struct");
+        };
+
+        // a row major mat4x3 is stored as 3 rows of 4 components
+        assert!(matches!(
+            members[2].layout.kind,
+            LayoutKind::Matrix {
+                columns: 4,
+                rows: 3,
+                stride: 16,
+                row_major: true,
+            }
+        ));
+        assert!(matches!(
+            members[3].layout.kind,
+            LayoutKind::Matrix {
+                columns: 4,
+                rows: 3,
+                stride: 16,
+                row_major: false,
+            }
+        ));
+    }
+
+    #[test]
+    fn array_strides() {
+        let module = std140_block();
+        let layout = module.layout(8, None).unwrap();
+        let LayoutKind::Struct { members, .. } = &layout.kind else {
+            panic!("not a struct");
+        };
+        assert!(matches!(
+            members[4].layout.kind,
+            LayoutKind::Array {
+                stride: 16,
+                length: Some(3),
+                ..
+            }
+        ));
+
+        assert_eq!(module.field_offset(8, "4[2]").unwrap().offset, 176);
+        assert_eq!(module.flatten_block(8).unwrap().len(), 7);
+    }
+
+    #[test]
+    fn physical_pointers_are_64_bit() {
+        // `struct { Ref ptr; uint x; }` where `Ref` is a `buffer_reference` to `struct { float f; }`
+        let mut asm = Assembler::new();
+        asm.op(72, &[11, 0, 35, 0])
+            .op(72, &[12, 0, 35, 0])
+            .op(72, &[12, 1, 35, 8])
+            .op(22, &[1, 32])
+            .op(21, &[5, 32, 0])
+            .op(39, &[10, 5349])
+            .op(30, &[12, 10, 5])
+            .op(30, &[11, 1])
+            .op(32, &[10, 5349, 11]);
+        let module = Module::from_words(asm.words()).unwrap();
+
+        let layout = module.layout(12, None).unwrap();
+        assert_eq!(members(&layout), [(0, 8), (8, 4)]);
+        assert_eq!((layout.size, layout.alignment), (12, 8));
+    }
+
+    #[test]
+    fn overflowing_array_size_is_rejected() {
+        // `float[1 << 30]` with an array stride of 16
+        let mut asm = Assembler::new();
+        asm.op(71, &[7, 6, 16])
+            .op(22, &[1, 32])
+            .op(21, &[5, 32, 0])
+            .op(43, &[5, 6, 1 << 30])
+            .op(28, &[7, 1, 6]);
+        let module = Module::from_words(asm.words()).unwrap();
+
+        assert!(module.layout(7, None).is_none());
+    }
+
+    // `struct { float values[]; }` with an array stride of 4 as type 3
+    fn runtime_array_block() -> Module {
+        let mut asm = Assembler::new();
+        asm.op(71, &[2, 6, 4])
+            .op(72, &[3, 0, 35, 0])
+            .op(22, &[1, 32])
+            .op(29, &[2, 1])
+            .op(30, &[3, 2]);
+        Module::from_words(asm.words()).unwrap()
+    }
+
+    #[test]
+    fn runtime_array_elements_are_addressable() {
+        let field = runtime_array_block().field_offset(3, "0[5]").unwrap();
+        assert_eq!((field.offset, field.size), (20, 4));
+    }
+
+    #[test]
+    fn overflowing_field_offset_is_rejected() {
+        let module = runtime_array_block();
+        assert!(module.field_offset(3, "0[2000000000]").is_none());
+        assert!(module.field_offset(3, "0[1073741824]").is_none());
+        assert!(module.field_offset(3, "0[1073741823]").is_some());
+    }
+}
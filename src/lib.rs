@@ -8,10 +8,49 @@
 
 use std::{collections::HashMap, str::Utf8Error};
 
+use constants::{resolve_size, respecialize_size, ConstantCache, ConstantKind, RawConstant};
 use ops::{Dim, Id, Op};
 use thiserror::Error;
 
+#[macro_use]
+mod macros;
+
+mod builtin;
+mod constants;
+mod execution_mode;
+mod fragment;
+mod image;
+mod info;
+mod interface;
+mod layout;
 mod ops;
+mod pipeline;
+mod sbt;
+#[cfg(test)]
+mod test_utils;
+mod vertex;
+
+pub use builtin::BuiltIn;
+pub use constants::{ConstantSize, ConstantValue, SpecializationConstant};
+pub use execution_mode::{
+    DepthCondition, FragmentExecutionModes, GeometryExecutionModes, GeometryInputPrimitive,
+    GeometryOutputPrimitive, TessellationExecutionModes, TessellationPrimitive,
+    TessellationSpacing, VertexOrder, WorkgroupSize,
+};
+pub use fragment::{ColorOutput, FragmentOutputs};
+pub use image::{AccessQualifier, ImageDepth, ImageDim, ImageFormat, ImageUsage, NumericType};
+pub use info::{AddressingModel, Capability, MemoryModel, ModuleInfo, Version};
+pub use interface::{check_interface, InterfaceMismatch};
+pub use layout::{BlockField, LayoutKind, MemberLayout, TypeLayout};
+pub use pipeline::{
+    DescriptorBinding, DescriptorSetLayout, PipelineLayout, PipelineLayoutBuilder,
+    PipelineLayoutError, PushConstantRange, ShaderStages,
+};
+pub use sbt::{
+    ShaderBindingTableBuilder, ShaderBindingTableError, ShaderBindingTableLayout, ShaderGroup,
+    ShaderGroupKind, ShaderGroupRegion,
+};
+pub use vertex::{VertexAttribute, VertexBinding, VertexBindingPolicy, VertexInputLayout};
 
 #[derive(Debug, Clone, Error)]
 pub enum Error {
@@ -30,16 +69,19 @@ pub enum Error {
 pub type SpirvResult<T> = ::std::result::Result<T, Error>;
 
 /// Stores the reflection info of a single SPIRV module.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Module {
+    info: ModuleInfo,
     types: HashMap<u32, Type>,
+    constants: HashMap<u32, RawConstant>,
     entry_points: Vec<EntryPoint>,
+    specialization_constants: Vec<SpecializationConstant>,
 }
 
 /// Describes a single `EntryPoint` in a SPIR-V module.
 ///
 /// A SPIR-V module can have multiple entry points with different names, each defining a single shader.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct EntryPoint {
     /// The name of the entry point, used for identification
     pub name: String,
@@ -47,12 +89,30 @@ pub struct EntryPoint {
     pub execution_model: ExecutionModel,
     /// All uniform variables used in this shader
     pub uniforms: Vec<UniformVariable>,
+    /// All input attachments used in this shader, these are also contained in `uniforms`
+    pub input_attachments: Vec<InputAttachment>,
     /// All push constant variables used in this shader
     pub push_constants: Vec<PushConstantVariable>,
     /// All inputs used in this shader
     pub inputs: Vec<LocationVariable>,
     /// All outputs used in this shader
     pub outputs: Vec<LocationVariable>,
+    /// All built-in inputs used in this shader, including members of input blocks
+    pub builtin_inputs: Vec<BuiltInVariable>,
+    /// All built-in outputs used in this shader, including members of output blocks (e.g. `gl_PerVertex`)
+    pub builtin_outputs: Vec<BuiltInVariable>,
+    /// All ray payloads, hit attributes, callable data and shader record buffers used in this ray tracing shader
+    pub ray_tracing_variables: Vec<RayTracingVariable>,
+    /// The local workgroup size of compute, mesh and task shaders (if declared)
+    pub workgroup_size: Option<WorkgroupSize>,
+    /// true if the shader traces rays with ray queries (requires the Vulkan `rayQuery` feature)
+    pub uses_ray_query: bool,
+    /// The execution modes of fragment shaders
+    pub fragment_modes: Option<FragmentExecutionModes>,
+    /// The execution modes of geometry shaders
+    pub geometry_modes: Option<GeometryExecutionModes>,
+    /// The execution modes of tessellation control and evaluation shaders
+    pub tessellation_modes: Option<TessellationExecutionModes>,
 }
 
 impl Module {
@@ -70,7 +130,17 @@ impl Module {
             return Err(Error::InvalidHeader);
         }
 
-        // Skip the rest of the header (Should be parsed in the future)
+        let mut info = ModuleInfo {
+            version: Version::from_word(words[1]),
+            generator: words[2],
+            bound: words[3],
+            schema: words[4],
+            capabilities: Vec::new(),
+            extensions: Vec::new(),
+            ext_inst_imports: Vec::new(),
+            addressing_model: None,
+            memory_model: None,
+        };
         words = &words[5..];
 
         // decode all opcodes
@@ -80,7 +150,7 @@ impl Module {
             ops.push(op);
         }
 
-        // All OpConstant values are stored in this Map
+        // All scalar OpConstant*, OpSpecConstant* and OpSpecConstantOp values are stored in this Map
         let mut constants = HashMap::new();
         // All type declarations are stored in this Map
         let mut types = HashMap::new();
@@ -88,26 +158,126 @@ impl Module {
         let mut vars = HashMap::new();
         // All entry points declarations are stored in this Vec
         let mut entries = Vec::new();
+        // The descriptor types of all opaque types (images, samplers, etc.) are stored in this Map
+        let mut descriptor_types = HashMap::new();
+        // The Block/BufferBlock decorations of all struct types are stored in this Map
+        let mut blocks = HashMap::new();
 
-        Self::collect_types_and_vars(&ops, &mut types, &mut constants, &mut vars, &mut entries)?;
-        Self::collect_decorations_and_names(&ops, &mut types, &mut vars);
+        Self::collect_types_and_vars(
+            &ops,
+            &mut types,
+            &mut constants,
+            &mut vars,
+            &mut entries,
+            &mut descriptor_types,
+        )?;
+        Self::collect_decorations_and_names(
+            &ops,
+            &mut types,
+            &mut vars,
+            &mut blocks,
+            &mut constants,
+        );
+        // array lengths can only be resolved to specialization constants once their SpecId decorations are known
+        let mut cache = ConstantCache::new();
+        Self::resolve_array_lengths(&mut types, &constants, &mut cache);
+        Self::collect_module_info(&ops, &mut info);
+        Self::collect_execution_modes(&ops, &mut entries);
+        Self::collect_ray_query_usage(&ops, &mut entries);
 
-        // uniforms are all variables that are a pointer with a storage class of Uniform or UniformConstant
+        // uniforms are all variables that are a pointer with a storage class of Uniform, UniformConstant or StorageBuffer
         let uniforms: HashMap<_, _> = vars
             .iter()
             .filter_map(|(id, var)| {
                 if let Some(Type::Pointer {
-                    storage_class: StorageClass::Uniform | StorageClass::UniformConstant,
+                    storage_class:
+                        storage_class @ (StorageClass::Uniform
+                        | StorageClass::UniformConstant
+                        | StorageClass::StorageBuffer),
                     pointed_type_id,
                 }) = types.get(&var.type_id)
                 {
+                    let (set, binding) = (var.set?, var.binding?);
+                    let descriptor_type = Self::get_descriptor_type(
+                        &types,
+                        &descriptor_types,
+                        &blocks,
+                        *storage_class,
+                        *pointed_type_id,
+                    )?;
+
                     Some((
                         *id,
                         UniformVariable {
-                            set: var.set?,
-                            binding: var.binding?,
+                            set,
+                            binding,
                             type_id: *pointed_type_id, // for convenience, we store the pointed-to type instead of the pointer, since every uniform is a pointer
                             name: var.name.clone(),
+                            descriptor_type,
+                            descriptor_count: Self::get_descriptor_count(&types, *pointed_type_id),
+                        },
+                    ))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        // input attachments are all uniforms of subpass data images with an InputAttachmentIndex decoration
+        let input_attachments: HashMap<_, _> = vars
+            .iter()
+            .filter_map(|(id, var)| {
+                let uniform = uniforms.get(id)?;
+                if uniform.descriptor_type != DescriptorType::InputAttachment {
+                    return None;
+                }
+
+                let mut type_id = uniform.type_id;
+                while let Some(Type::Array {
+                    element_type_id, ..
+                }) = types.get(&type_id)
+                {
+                    type_id = *element_type_id;
+                }
+                let Some(Type::Image { multisampled, .. }) = types.get(&type_id) else {
+                    return None;
+                };
+
+                Some((
+                    *id,
+                    InputAttachment {
+                        attachment_index: var.input_attachment_index?,
+                        set: uniform.set,
+                        binding: uniform.binding,
+                        type_id: uniform.type_id,
+                        name: var.name.clone(),
+                        multisampled: *multisampled,
+                    },
+                ))
+            })
+            .collect();
+
+        let ray_tracing_variables: HashMap<_, _> = vars
+            .iter()
+            .filter_map(|(id, var)| {
+                if let Some(Type::Pointer {
+                    storage_class:
+                        storage_class @ (StorageClass::CallableData
+                        | StorageClass::IncomingCallableData
+                        | StorageClass::RayPayload
+                        | StorageClass::HitAttribute
+                        | StorageClass::IncomingRayPayload
+                        | StorageClass::ShaderRecordBuffer),
+                    pointed_type_id,
+                }) = types.get(&var.type_id)
+                {
+                    Some((
+                        *id,
+                        RayTracingVariable {
+                            storage_class: *storage_class,
+                            location: var.location,
+                            type_id: *pointed_type_id,
+                            name: var.name.clone(),
                         },
                     ))
                 } else {
@@ -149,6 +319,12 @@ impl Module {
                         *id,
                         LocationVariable {
                             location: var.location?,
+                            component: var.component,
+                            interpolation: var.interpolation,
+                            sampling: var.sampling,
+                            patch: var.patch,
+                            per_primitive: var.per_primitive,
+                            index: var.index,
                             type_id: *pointed_type_id,
                             name: var.name.clone(),
                         },
@@ -171,6 +347,12 @@ impl Module {
                         *id,
                         LocationVariable {
                             location: var.location?,
+                            component: var.component,
+                            interpolation: var.interpolation,
+                            sampling: var.sampling,
+                            patch: var.patch,
+                            per_primitive: var.per_primitive,
+                            index: var.index,
                             type_id: *pointed_type_id,
                             name: var.name.clone(),
                         },
@@ -181,6 +363,45 @@ impl Module {
             })
             .collect();
 
+        // built-in inputs and outputs are either decorated variables or members of an I/O block
+        let builtins: HashMap<_, _> = vars
+            .iter()
+            .filter_map(|(id, var)| {
+                if let Some(Type::Pointer {
+                    storage_class: storage_class @ (StorageClass::Input | StorageClass::Output),
+                    pointed_type_id,
+                }) = types.get(&var.type_id)
+                {
+                    Some((
+                        *id,
+                        (
+                            *storage_class,
+                            Self::get_builtin_variables(&types, var, *pointed_type_id),
+                        ),
+                    ))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        let mut specialization_constants: Vec<_> = constants
+            .values()
+            .filter(|c| c.specialization)
+            .filter_map(|c| {
+                Some(SpecializationConstant {
+                    spec_id: c.spec_id?,
+                    name: c.name.clone(),
+                    type_id: c.type_id,
+                    default: match c.kind {
+                        ConstantKind::Scalar(value) => value,
+                        _ => return None,
+                    },
+                })
+            })
+            .collect();
+        specialization_constants.sort_by_key(|c| c.spec_id);
+
         let entry_points = entries
             .iter()
             .map(|e| {
@@ -189,6 +410,11 @@ impl Module {
                     .iter()
                     .filter_map(|id| uniforms.get(&id.0).cloned())
                     .collect();
+                let input_attachments = e
+                    .interface
+                    .iter()
+                    .filter_map(|id| input_attachments.get(&id.0).cloned())
+                    .collect();
                 let push_constants = e
                     .interface
                     .iter()
@@ -204,24 +430,127 @@ impl Module {
                     .iter()
                     .filter_map(|id| outputs.get(&id.0).cloned())
                     .collect();
+                let ray_tracing_variables = e
+                    .interface
+                    .iter()
+                    .filter_map(|id| ray_tracing_variables.get(&id.0).cloned())
+                    .collect();
+                let builtins_of = |class: StorageClass| {
+                    e.interface
+                        .iter()
+                        .filter_map(|id| builtins.get(&id.0))
+                        .filter(|(storage_class, _)| *storage_class == class)
+                        .flat_map(|(_, vars)| vars.iter().cloned())
+                        .collect()
+                };
 
                 EntryPoint {
                     name: e.name.clone(),
                     execution_model: e.execution_model,
                     uniforms,
+                    input_attachments,
                     push_constants,
                     inputs,
                     outputs,
+                    builtin_inputs: builtins_of(StorageClass::Input),
+                    builtin_outputs: builtins_of(StorageClass::Output),
+                    ray_tracing_variables,
+                    workgroup_size: Self::get_workgroup_size(&types, &constants, e, &mut cache),
+                    uses_ray_query: e.uses_ray_query,
+                    fragment_modes: (e.execution_model == ExecutionModel::Fragment)
+                        .then_some(e.fragment_modes),
+                    geometry_modes: (e.execution_model == ExecutionModel::Geometry)
+                        .then_some(e.geometry_modes),
+                    tessellation_modes: matches!(
+                        e.execution_model,
+                        ExecutionModel::TessellationControl
+                            | ExecutionModel::TessellationEvaluation
+                    )
+                    .then_some(e.tessellation_modes),
                 }
             })
             .collect();
 
         Ok(Self {
+            info,
             types,
+            constants,
             entry_points,
+            specialization_constants,
         })
     }
 
+    /// Returns the [`ModuleInfo`] describing the header and module-level declarations of the SPIR-V module
+    pub fn get_info(&self) -> &ModuleInfo {
+        &self.info
+    }
+
+    /// Returns all specialization constants declared in the SPIR-V module, ordered by their `spec_id`
+    pub fn specialization_constants(&self) -> &[SpecializationConstant] {
+        &self.specialization_constants
+    }
+
+    /// Returns a copy of the module in which the specialization constants are set to the given `values`.
+    ///
+    /// `values` maps the `spec_id` of a [`SpecializationConstant`] to its new value, constants not contained in
+    /// `values` keep their current value. Array lengths and descriptor counts depending on specialization constants
+    /// are re-evaluated, so the sizes and layouts reported by the returned module reflect the chosen values.
+    #[must_use]
+    pub fn specialize(&self, values: &HashMap<u32, ConstantValue>) -> Module {
+        let mut module = self.clone();
+
+        for constant in module.constants.values_mut() {
+            let (true, Some(spec_id), ConstantKind::Scalar(value)) = (
+                constant.specialization,
+                constant.spec_id,
+                &mut constant.kind,
+            ) else {
+                continue;
+            };
+
+            let new_value = values
+                .get(&spec_id)
+                .zip(module.types.get(&constant.type_id))
+                .and_then(|(value, ty)| value.cast(ty));
+            if let Some(new_value) = new_value {
+                *value = new_value;
+            }
+        }
+
+        for spec_constant in &mut module.specialization_constants {
+            if let Some(ConstantKind::Scalar(value)) = module
+                .constants
+                .values()
+                .find(|c| c.spec_id == Some(spec_constant.spec_id))
+                .map(|c| &c.kind)
+            {
+                spec_constant.default = *value;
+            }
+        }
+
+        let mut cache = ConstantCache::new();
+        Self::resolve_array_lengths(&mut module.types, &module.constants, &mut cache);
+
+        for entry_point in &mut module.entry_points {
+            for uniform in &mut entry_point.uniforms {
+                uniform.descriptor_count =
+                    Self::get_descriptor_count(&module.types, uniform.type_id);
+            }
+
+            if let Some(size) = &mut entry_point.workgroup_size {
+                for dimension in [&mut size.x, &mut size.y, &mut size.z] {
+                    if let Some(new_dimension) =
+                        respecialize_size(&module.constants, &module.types, *dimension, &mut cache)
+                    {
+                        *dimension = new_dimension;
+                    }
+                }
+            }
+        }
+
+        module
+    }
+
     /// Returns the [`Type`] definition indicated by `type_id`, or `None` if `type_id` is not a type.
     pub fn get_type(&self, type_id: u32) -> Option<&Type> {
         self.types.get(&type_id)
@@ -232,46 +561,507 @@ impl Module {
         &self.entry_points
     }
 
-    fn get_type_size(&self, type_id: u32, stride: Option<u32>) -> Option<u32> {
-        if let Some(ty) = self.types.get(&type_id) {
-            match ty {
-                Type::Int32 | Type::UInt32 | Type::Float32 => Some(4),
-                Type::Vec2 => Some(8),
-                Type::Vec3 => Some(12),
-                Type::Vec4 => Some(16),
-                Type::Mat3 => stride.map(|stride| stride * 2 + 12), // two rows/columns + sizeof(Vec3)
-                Type::Mat4 => stride.map(|stride| stride * 3 + 16), // three rows/columns + sizeof(Vec4)
-                Type::Struct { elements, .. } => {
-                    // Since there is no Size Decoration in SPIRV that tells us the size,
-                    // we calculate it from the offset of the last member and its size.
-                    let last_element = elements.iter().max_by_key(|e| e.offset.unwrap_or(0))?;
-                    let offset = last_element.offset?;
-                    let size = self.get_member_size(last_element)?;
-
-                    Some(offset + size)
-                }
-                _ => None,
+    /// Checks whether `type_id` and `other_type_id` of `other` describe the same type with the same memory layout.
+    ///
+    /// Names are ignored, since they are usually not consistent between shader stages.
+    pub(crate) fn is_type_compatible(
+        &self,
+        type_id: u32,
+        other: &Module,
+        other_type_id: u32,
+    ) -> bool {
+        let (Some(a), Some(b)) = (self.get_type(type_id), other.get_type(other_type_id)) else {
+            return false;
+        };
+
+        match (a, b) {
+            (
+                Type::Image {
+                    sampled_type_id,
+                    dim,
+                    depth,
+                    arrayed,
+                    multisampled,
+                    sampled,
+                    format,
+                    access: _,
+                },
+                Type::Image {
+                    sampled_type_id: other_sampled_type_id,
+                    dim: other_dim,
+                    depth: other_depth,
+                    arrayed: other_arrayed,
+                    multisampled: other_multisampled,
+                    sampled: other_sampled,
+                    format: other_format,
+                    access: _,
+                },
+            ) => {
+                dim == other_dim
+                    && depth == other_depth
+                    && arrayed == other_arrayed
+                    && multisampled == other_multisampled
+                    && sampled == other_sampled
+                    && format == other_format
+                    && self.is_type_compatible(*sampled_type_id, other, *other_sampled_type_id)
+            }
+            (
+                Type::SampledImage { image_type_id },
+                Type::SampledImage {
+                    image_type_id: other_image_type_id,
+                },
+            ) => self.is_type_compatible(*image_type_id, other, *other_image_type_id),
+            (
+                Type::Array {
+                    element_type_id,
+                    length,
+                    stride,
+                },
+                Type::Array {
+                    element_type_id: other_element_type_id,
+                    length: other_length,
+                    stride: other_stride,
+                },
+            ) => {
+                // constant ids are not comparable between modules, so only the resulting lengths are compared
+                length.value() == other_length.value()
+                    && stride == other_stride
+                    && self.is_type_compatible(*element_type_id, other, *other_element_type_id)
+            }
+            (
+                Type::Struct { elements, .. },
+                Type::Struct {
+                    elements: other_elements,
+                    ..
+                },
+            ) => {
+                elements.len() == other_elements.len()
+                    && elements.iter().zip(other_elements).all(|(e, o)| {
+                        e.offset == o.offset
+                            && e.row_major == o.row_major
+                            && e.stride == o.stride
+                            && self.is_type_compatible(e.type_id, other, o.type_id)
+                    })
+            }
+            (
+                Type::Pointer {
+                    storage_class,
+                    pointed_type_id,
+                },
+                Type::Pointer {
+                    storage_class: other_storage_class,
+                    pointed_type_id: other_pointed_type_id,
+                },
+            ) => {
+                // physical pointers are plain addresses and may point to their own struct (e.g. linked lists)
+                storage_class == other_storage_class
+                    && (*storage_class == StorageClass::PhysicalStorageBuffer
+                        || self.is_type_compatible(*pointed_type_id, other, *other_pointed_type_id))
+            }
+            (
+                Type::Int { width, signed },
+                Type::Int {
+                    width: other_width,
+                    signed: other_signed,
+                },
+            ) => width == other_width && signed == other_signed,
+            (Type::Float { width }, Type::Float { width: other_width }) => width == other_width,
+            (
+                Type::Matrix {
+                    column_type_id,
+                    columns,
+                },
+                Type::Matrix {
+                    column_type_id: other_column_type_id,
+                    columns: other_columns,
+                },
+            ) => {
+                columns == other_columns
+                    && self.is_type_compatible(*column_type_id, other, *other_column_type_id)
             }
-        } else {
-            None
+            (
+                Type::Vector {
+                    component_type_id,
+                    count,
+                },
+                Type::Vector {
+                    component_type_id: other_component_type_id,
+                    count: other_count,
+                },
+            ) => {
+                count == other_count
+                    && self.is_type_compatible(*component_type_id, other, *other_component_type_id)
+            }
+            _ => std::mem::discriminant(a) == std::mem::discriminant(b),
+        }
+    }
+
+    fn get_type_size(&self, type_id: u32, member: Option<&StructMember>) -> Option<u32> {
+        self.layout(type_id, member).map(|layout| layout.size)
+    }
+
+    /// Derives the [`DescriptorType`] of a uniform variable from its storage class and pointed-to type.
+    ///
+    /// Arrays of descriptors are unwrapped, so the returned type describes a single array element.
+    fn get_descriptor_type(
+        types: &HashMap<u32, Type>,
+        descriptor_types: &HashMap<u32, DescriptorType>,
+        blocks: &HashMap<u32, BlockDecoration>,
+        storage_class: StorageClass,
+        type_id: u32,
+    ) -> Option<DescriptorType> {
+        match types.get(&type_id)? {
+            Type::Array {
+                element_type_id, ..
+            } => Self::get_descriptor_type(
+                types,
+                descriptor_types,
+                blocks,
+                storage_class,
+                *element_type_id,
+            ),
+            Type::Struct { .. } => match storage_class {
+                // SPIR-V 1.3+ declares SSBOs in their own storage class
+                StorageClass::StorageBuffer => Some(DescriptorType::StorageBuffer),
+                // before SPIR-V 1.3, SSBOs were Uniform blocks decorated with BufferBlock
+                StorageClass::Uniform => match blocks.get(&type_id) {
+                    Some(BlockDecoration::BufferBlock) => Some(DescriptorType::StorageBuffer),
+                    Some(BlockDecoration::Block) | None => Some(DescriptorType::UniformBuffer),
+                },
+                _ => None,
+            },
+            _ => descriptor_types.get(&type_id).copied(),
+        }
+    }
+
+    /// Calculates the number of descriptors bound by a uniform variable of type `type_id`.
+    ///
+    /// Arrays of arrays are flattened into a single count, which fails if the count does not fit into a `u32`.
+    fn get_descriptor_count(types: &HashMap<u32, Type>, type_id: u32) -> DescriptorCount {
+        match types.get(&type_id) {
+            Some(Type::Array {
+                element_type_id,
+                length,
+                ..
+            }) => match (length, Self::get_descriptor_count(types, *element_type_id)) {
+                (ArrayLength::Runtime, _) => DescriptorCount::Runtime,
+                (length, DescriptorCount::Fixed(count)) => match length.value() {
+                    Some(length) => length
+                        .checked_mul(count)
+                        .map_or(DescriptorCount::Overflow, DescriptorCount::Fixed),
+                    // the array still has a fixed length, it just cannot be evaluated
+                    None => DescriptorCount::Unknown,
+                },
+                (_, count) => count,
+            },
+            _ => DescriptorCount::Fixed(1),
         }
     }
 
     /// Returns the size of a given [`StructMember`], if known.
     pub fn get_member_size(&self, member: &StructMember) -> Option<u32> {
-        self.get_type_size(member.type_id, Some(member.stride))
+        self.get_type_size(member.type_id, Some(member))
     }
 
     /// Returns the size of a given [`UniformVariable`], [`PushConstantVariable`] or [`LocationVariable`], if known.
+    ///
+    /// Runtime arrays do not contribute to the size, so the size of a storage buffer ending in a runtime array
+    /// is the size of all the members preceding the array.
     pub fn get_var_size<T: Variable>(&self, var: &T) -> Option<u32> {
         self.get_type_size(var.get_type_id(), None)
     }
 
+    /// Determines the [`WorkgroupSize`] of an entry point.
+    ///
+    /// A constant decorated with the `WorkgroupSize` built-in overrides the `LocalSize` and `LocalSizeId` execution modes.
+    fn get_workgroup_size(
+        types: &HashMap<u32, Type>,
+        constants: &HashMap<u32, RawConstant>,
+        entry: &RawEntryPoint,
+        cache: &mut ConstantCache,
+    ) -> Option<WorkgroupSize> {
+        let builtin = constants
+            .values()
+            .filter(|c| c.builtin == Some(BuiltIn::WorkgroupSize))
+            .find_map(|c| match &c.kind {
+                ConstantKind::Composite(constituents) => {
+                    <[u32; 3]>::try_from(&constituents[..]).ok()
+                }
+                _ => None,
+            })
+            .filter(|_| {
+                matches!(
+                    entry.execution_model,
+                    ExecutionModel::GLCompute
+                        | ExecutionModel::Kernel
+                        | ExecutionModel::TaskNV
+                        | ExecutionModel::MeshNV
+                        | ExecutionModel::TaskEXT
+                        | ExecutionModel::MeshEXT
+                )
+            });
+
+        let [x, y, z] = match (builtin.or(entry.local_size_id), entry.local_size) {
+            (Some(ids), _) => ids.map(|id| resolve_size(constants, types, id, cache)),
+            (None, Some(sizes)) => sizes.map(|size| Some(ConstantSize::Fixed(size))),
+            (None, None) => return None,
+        };
+
+        Some(WorkgroupSize {
+            x: x?,
+            y: y?,
+            z: z?,
+        })
+    }
+
+    /// Lists the built-ins of the input or output variable `var`, either the variable itself or the members of an I/O block
+    fn get_builtin_variables(
+        types: &HashMap<u32, Type>,
+        var: &RawVariable,
+        pointed_type_id: u32,
+    ) -> Vec<BuiltInVariable> {
+        if let Some(builtin) = var.builtin {
+            return vec![BuiltInVariable {
+                builtin,
+                type_id: pointed_type_id,
+                name: var.name.clone(),
+                member: None,
+            }];
+        }
+
+        // I/O blocks of tessellation and geometry shaders are arrayed per vertex
+        let mut type_id = pointed_type_id;
+        while let Some(Type::Array {
+            element_type_id, ..
+        }) = types.get(&type_id)
+        {
+            type_id = *element_type_id;
+        }
+
+        match types.get(&type_id) {
+            Some(Type::Struct { elements, .. }) => elements
+                .iter()
+                .zip(0..)
+                .filter_map(|(e, i)| {
+                    Some(BuiltInVariable {
+                        builtin: e.builtin?,
+                        type_id: e.type_id,
+                        name: e.name.clone(),
+                        member: Some(i),
+                    })
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Re-evaluates the lengths of all arrays whose length depends on specialization constants
+    fn resolve_array_lengths(
+        types: &mut HashMap<u32, Type>,
+        constants: &HashMap<u32, RawConstant>,
+        cache: &mut ConstantCache,
+    ) {
+        let lengths: Vec<_> = types
+            .iter()
+            .filter_map(|(id, t)| match t {
+                Type::Array {
+                    length: ArrayLength::Constant(size),
+                    ..
+                } => Some((*id, respecialize_size(constants, types, *size, cache)?)),
+                _ => None,
+            })
+            .collect();
+
+        for (id, new_length) in lengths {
+            if let Some(Type::Array { length, .. }) = types.get_mut(&id) {
+                *length = ArrayLength::Constant(new_length);
+            }
+        }
+    }
+
+    // Finds the entry points that initialize ray queries, either directly or in any function they call
+    fn collect_ray_query_usage(ops: &[Op], entries: &mut [RawEntryPoint]) {
+        let mut function = None;
+        // function id => ids of all functions it calls
+        let mut calls: HashMap<u32, Vec<u32>> = HashMap::new();
+        let mut ray_query_functions = Vec::new();
+
+        for op in ops {
+            match op {
+                Op::OpFunction { result, .. } => function = Some(result.0),
+                Op::OpFunctionEnd {} => function = None,
+                Op::OpFunctionCall {
+                    function: callee, ..
+                } => {
+                    if let Some(function) = function {
+                        calls.entry(function).or_default().push(callee.0);
+                    }
+                }
+                Op::OpRayQueryInitializeKHR { .. } => {
+                    ray_query_functions.extend(function);
+                }
+                _ => {}
+            }
+        }
+
+        for entry in entries {
+            let mut visited = vec![entry.func];
+            let mut pending = vec![entry.func];
+            while let Some(function) = pending.pop() {
+                if ray_query_functions.contains(&function) {
+                    entry.uses_ray_query = true;
+                    break;
+                }
+                for callee in calls.get(&function).into_iter().flatten() {
+                    if !visited.contains(callee) {
+                        visited.push(*callee);
+                        pending.push(*callee);
+                    }
+                }
+            }
+        }
+    }
+
+    // Parses all the OpExecutionMode and OpExecutionModeId instructions
+    fn collect_execution_modes(ops: &[Op], entries: &mut [RawEntryPoint]) {
+        for op in ops {
+            if let Op::OpExecutionMode { entry_point, mode }
+            | Op::OpExecutionModeId { entry_point, mode } = op
+            {
+                for entry in entries.iter_mut().filter(|e| e.func == entry_point.0) {
+                    match mode {
+                        ops::ExecutionMode::LocalSize { x, y, z } => {
+                            entry.local_size = Some([*x, *y, *z]);
+                        }
+                        ops::ExecutionMode::LocalSizeId { x, y, z } => {
+                            entry.local_size_id = Some([x.0, y.0, z.0]);
+                        }
+                        ops::ExecutionMode::OriginUpperLeft {} => {
+                            entry.fragment_modes.origin_upper_left = true;
+                        }
+                        ops::ExecutionMode::EarlyFragmentTests {} => {
+                            entry.fragment_modes.early_fragment_tests = true;
+                        }
+                        ops::ExecutionMode::DepthReplacing {} => {
+                            entry.fragment_modes.depth_replacing = true;
+                        }
+                        ops::ExecutionMode::DepthGreater {} => {
+                            entry.fragment_modes.depth_condition = Some(DepthCondition::Greater);
+                        }
+                        ops::ExecutionMode::DepthLess {} => {
+                            entry.fragment_modes.depth_condition = Some(DepthCondition::Less);
+                        }
+                        ops::ExecutionMode::DepthUnchanged {} => {
+                            entry.fragment_modes.depth_condition = Some(DepthCondition::Unchanged);
+                        }
+                        ops::ExecutionMode::InputPoints {} => {
+                            entry.geometry_modes.input_primitive =
+                                Some(GeometryInputPrimitive::Points);
+                        }
+                        ops::ExecutionMode::InputLines {} => {
+                            entry.geometry_modes.input_primitive =
+                                Some(GeometryInputPrimitive::Lines);
+                        }
+                        ops::ExecutionMode::InputLinesAdjacency {} => {
+                            entry.geometry_modes.input_primitive =
+                                Some(GeometryInputPrimitive::LinesAdjacency);
+                        }
+                        ops::ExecutionMode::Triangles {} => {
+                            // shared by the geometry input primitive and the tessellation domain
+                            entry.geometry_modes.input_primitive =
+                                Some(GeometryInputPrimitive::Triangles);
+                            entry.tessellation_modes.primitive =
+                                Some(TessellationPrimitive::Triangles);
+                        }
+                        ops::ExecutionMode::InputTrianglesAdjacency {} => {
+                            entry.geometry_modes.input_primitive =
+                                Some(GeometryInputPrimitive::TrianglesAdjacency);
+                        }
+                        ops::ExecutionMode::Quads {} => {
+                            entry.tessellation_modes.primitive = Some(TessellationPrimitive::Quads);
+                        }
+                        ops::ExecutionMode::Isolines {} => {
+                            entry.tessellation_modes.primitive =
+                                Some(TessellationPrimitive::Isolines);
+                        }
+                        ops::ExecutionMode::OutputVertices { count } => {
+                            entry.geometry_modes.output_vertices = Some(*count);
+                            entry.tessellation_modes.output_vertices = Some(*count);
+                        }
+                        ops::ExecutionMode::OutputPoints {} => {
+                            entry.geometry_modes.output_primitive =
+                                Some(GeometryOutputPrimitive::Points);
+                        }
+                        ops::ExecutionMode::OutputLineStrip {} => {
+                            entry.geometry_modes.output_primitive =
+                                Some(GeometryOutputPrimitive::LineStrip);
+                        }
+                        ops::ExecutionMode::OutputTriangleStrip {} => {
+                            entry.geometry_modes.output_primitive =
+                                Some(GeometryOutputPrimitive::TriangleStrip);
+                        }
+                        ops::ExecutionMode::Invocations { count } => {
+                            entry.geometry_modes.invocations = Some(*count);
+                        }
+                        ops::ExecutionMode::SpacingEqual {} => {
+                            entry.tessellation_modes.spacing = Some(TessellationSpacing::Equal);
+                        }
+                        ops::ExecutionMode::SpacingFractionalEven {} => {
+                            entry.tessellation_modes.spacing =
+                                Some(TessellationSpacing::FractionalEven);
+                        }
+                        ops::ExecutionMode::SpacingFractionalOdd {} => {
+                            entry.tessellation_modes.spacing =
+                                Some(TessellationSpacing::FractionalOdd);
+                        }
+                        ops::ExecutionMode::VertexOrderCw {} => {
+                            entry.tessellation_modes.vertex_order = Some(VertexOrder::Cw);
+                        }
+                        ops::ExecutionMode::VertexOrderCcw {} => {
+                            entry.tessellation_modes.vertex_order = Some(VertexOrder::Ccw);
+                        }
+                        ops::ExecutionMode::PointMode {} => {
+                            entry.tessellation_modes.point_mode = true;
+                        }
+                        ops::ExecutionMode::Unknown(_) => {}
+                    }
+                }
+            }
+        }
+    }
+
+    // Parses all the OpCapability, OpExtension, OpExtInstImport and OpMemoryModel instructions
+    fn collect_module_info(ops: &[Op], info: &mut ModuleInfo) {
+        for op in ops {
+            match op {
+                Op::OpCapability { capability } => {
+                    info.capabilities.push(Capability::from_code(*capability));
+                }
+                Op::OpExtension { name } => {
+                    info.extensions.push(name.clone());
+                }
+                Op::OpExtInstImport { result: _, name } => {
+                    info.ext_inst_imports.push(name.clone());
+                }
+                Op::OpMemoryModel {
+                    addressing_model,
+                    memory_model,
+                } => {
+                    info.addressing_model = Some(AddressingModel::from_code(*addressing_model));
+                    info.memory_model = Some(MemoryModel::from_code(*memory_model));
+                }
+                _ => {}
+            }
+        }
+    }
+
     /// Parses all the Op*Decoration and Op*Name instructions
     fn collect_decorations_and_names(
         ops: &[Op],
         types: &mut HashMap<u32, Type>,
         vars: &mut HashMap<u32, RawVariable>,
+        blocks: &mut HashMap<u32, BlockDecoration>,
+        constants: &mut HashMap<u32, RawConstant>,
     ) {
         for op in ops {
             match op {
@@ -280,6 +1070,8 @@ impl Module {
                         target.name = Some(name.clone());
                     } else if let Some(Type::Struct { name: n, .. }) = types.get_mut(&target.0) {
                         *n = Some(name.clone());
+                    } else if let Some(target) = constants.get_mut(&target.0) {
+                        target.name = Some(name.clone());
                     }
                 }
                 Op::OpMemberName {
@@ -309,6 +1101,74 @@ impl Module {
                             target.location = Some(*loc);
                         }
                     }
+                    ops::Decoration::Component { component } => {
+                        if let Some(target) = vars.get_mut(&target.0) {
+                            target.component = Some(*component);
+                        }
+                    }
+                    ops::Decoration::Flat {} => {
+                        if let Some(target) = vars.get_mut(&target.0) {
+                            target.interpolation = Interpolation::Flat;
+                        }
+                    }
+                    ops::Decoration::NoPerspective {} => {
+                        if let Some(target) = vars.get_mut(&target.0) {
+                            target.interpolation = Interpolation::NoPerspective;
+                        }
+                    }
+                    ops::Decoration::Centroid {} => {
+                        if let Some(target) = vars.get_mut(&target.0) {
+                            target.sampling = Sampling::Centroid;
+                        }
+                    }
+                    ops::Decoration::Sample {} => {
+                        if let Some(target) = vars.get_mut(&target.0) {
+                            target.sampling = Sampling::Sample;
+                        }
+                    }
+                    ops::Decoration::Patch {} => {
+                        if let Some(target) = vars.get_mut(&target.0) {
+                            target.patch = true;
+                        }
+                    }
+                    ops::Decoration::PerPrimitiveEXT {} => {
+                        if let Some(target) = vars.get_mut(&target.0) {
+                            target.per_primitive = true;
+                        }
+                    }
+                    ops::Decoration::Index { index } => {
+                        if let Some(target) = vars.get_mut(&target.0) {
+                            target.index = Some(*index);
+                        }
+                    }
+                    ops::Decoration::InputAttachmentIndex { index } => {
+                        if let Some(target) = vars.get_mut(&target.0) {
+                            target.input_attachment_index = Some(*index);
+                        }
+                    }
+                    ops::Decoration::Block {} => {
+                        blocks.insert(target.0, BlockDecoration::Block);
+                    }
+                    ops::Decoration::BufferBlock {} => {
+                        blocks.insert(target.0, BlockDecoration::BufferBlock);
+                    }
+                    ops::Decoration::BuiltIn { builtin } => {
+                        if let Some(target) = vars.get_mut(&target.0) {
+                            target.builtin = Some(BuiltIn::from_code(*builtin));
+                        } else if let Some(target) = constants.get_mut(&target.0) {
+                            target.builtin = Some(BuiltIn::from_code(*builtin));
+                        }
+                    }
+                    ops::Decoration::SpecId { spec_id } => {
+                        if let Some(target) = constants.get_mut(&target.0) {
+                            target.spec_id = Some(*spec_id);
+                        }
+                    }
+                    ops::Decoration::ArrayStride { stride } => {
+                        if let Some(Type::Array { stride: s, .. }) = types.get_mut(&target.0) {
+                            *s = Some(*stride);
+                        }
+                    }
                     _ => {}
                 },
                 Op::OpMemberDecorate {
@@ -326,11 +1186,15 @@ impl Module {
                                     elements[*member_index as usize].row_major = false;
                                 }
                                 ops::Decoration::MatrixStride { stride } => {
-                                    elements[*member_index as usize].stride = *stride;
+                                    elements[*member_index as usize].stride = Some(*stride);
                                 }
                                 ops::Decoration::Offset { offset } => {
                                     elements[*member_index as usize].offset = Some(*offset);
                                 }
+                                ops::Decoration::BuiltIn { builtin } => {
+                                    elements[*member_index as usize].builtin =
+                                        Some(BuiltIn::from_code(*builtin));
+                                }
                                 _ => {}
                             }
                         }
@@ -345,10 +1209,14 @@ impl Module {
     fn collect_types_and_vars(
         ops: &[Op],
         types: &mut HashMap<u32, Type>,
-        constants: &mut HashMap<u32, u32>,
+        constants: &mut HashMap<u32, RawConstant>,
         vars: &mut HashMap<u32, RawVariable>,
         entries: &mut Vec<RawEntryPoint>,
+        descriptor_types: &mut HashMap<u32, DescriptorType>,
     ) -> SpirvResult<()> {
+        // the values of constants do not change while they are collected
+        let mut cache = ConstantCache::new();
+
         for op in ops {
             match op {
                 Op::OpTypeVoid { result } => {
@@ -362,95 +1230,108 @@ impl Module {
                     width,
                     signed,
                 } => {
-                    if *width != 32 {
-                        types.insert(result.0, Type::Unknown);
-                    } else if *signed == 0 {
-                        types.insert(result.0, Type::UInt32);
-                    } else {
-                        types.insert(result.0, Type::Int32);
-                    }
+                    types.insert(
+                        result.0,
+                        Type::Int {
+                            width: *width,
+                            signed: *signed != 0,
+                        },
+                    );
                 }
                 Op::OpTypeFloat { result, width } => {
-                    if *width == 32 {
-                        types.insert(result.0, Type::Float32);
-                    } else {
-                        types.insert(result.0, Type::Unknown);
-                    }
+                    types.insert(result.0, Type::Float { width: *width });
                 }
                 Op::OpTypeVector {
                     result,
                     component_type,
                     component_count,
                 } => {
-                    if let Some(t) = types.get(&component_type.0) {
-                        if let Type::Float32 = t {
-                            match component_count {
-                                2 => {
-                                    types.insert(result.0, Type::Vec2);
-                                }
-                                3 => {
-                                    types.insert(result.0, Type::Vec3);
-                                }
-                                4 => {
-                                    types.insert(result.0, Type::Vec4);
-                                }
-                                _ => {
-                                    types.insert(result.0, Type::Unknown);
-                                }
-                            }
-                        } else {
-                            types.insert(result.0, Type::Unknown);
-                        }
-                    } else {
+                    if !types.contains_key(&component_type.0) {
                         return Err(Error::InvalidId);
                     }
+                    types.insert(
+                        result.0,
+                        Type::Vector {
+                            component_type_id: component_type.0,
+                            count: *component_count,
+                        },
+                    );
                 }
                 Op::OpTypeMatrix {
                     result,
                     column_type,
                     column_count,
                 } => {
-                    let t = types
-                        .get(&column_type.0)
-                        .map(|column_type| match column_type {
-                            Type::Vec3 if *column_count == 3 => Type::Mat3,
-                            Type::Vec4 if *column_count == 4 => Type::Mat4,
-                            _ => Type::Unknown,
-                        })
-                        .unwrap_or(Type::Unknown);
-                    types.insert(result.0, t);
+                    if !types.contains_key(&column_type.0) {
+                        return Err(Error::InvalidId);
+                    }
+                    types.insert(
+                        result.0,
+                        Type::Matrix {
+                            column_type_id: column_type.0,
+                            columns: *column_count,
+                        },
+                    );
                 }
                 Op::OpTypeImage {
                     result,
                     sampled_type,
                     dim,
                     depth,
-                    arrayed: _,
-                    ms: _,
+                    arrayed,
+                    ms,
                     sampled,
                     format,
-                    access: _,
+                    access,
                 } => {
-                    let t = if let Some(Type::Float32) = types.get(&sampled_type.0) {
-                        if let Dim::D2 {} = dim {
-                            Type::Image2D {
-                                depth: *depth != 0,
-                                sampled: *sampled != 0,
-                                format: *format,
-                            }
-                        } else {
-                            Type::Unknown
-                        }
-                    } else {
-                        Type::Unknown
+                    let descriptor_type = match dim {
+                        Dim::Buffer {} if *sampled == 2 => DescriptorType::StorageTexelBuffer,
+                        Dim::Buffer {} => DescriptorType::UniformTexelBuffer,
+                        Dim::SubpassData {} => DescriptorType::InputAttachment,
+                        _ if *sampled == 2 => DescriptorType::StorageImage,
+                        _ => DescriptorType::SampledImage,
                     };
-                    types.insert(result.0, t);
+                    descriptor_types.insert(result.0, descriptor_type);
+
+                    types.insert(
+                        result.0,
+                        Type::Image {
+                            sampled_type_id: sampled_type.0,
+                            dim: match dim {
+                                Dim::Unknown(code) => ImageDim::from_code(*code),
+                                Dim::D1 {} => ImageDim::D1,
+                                Dim::D2 {} => ImageDim::D2,
+                                Dim::D3 {} => ImageDim::D3,
+                                Dim::Cube {} => ImageDim::Cube,
+                                Dim::Rect {} => ImageDim::Rect,
+                                Dim::Buffer {} => ImageDim::Buffer,
+                                Dim::SubpassData {} => ImageDim::SubpassData,
+                            },
+                            depth: ImageDepth::from_code(*depth),
+                            arrayed: *arrayed != 0,
+                            multisampled: *ms != 0,
+                            sampled: ImageUsage::from_code(*sampled),
+                            format: (*format != 0).then(|| ImageFormat::from_code(*format)),
+                            access: access.map(AccessQualifier::from_code),
+                        },
+                    );
                 }
                 Op::OpTypeSampler { result } => {
                     types.insert(result.0, Type::Sampler);
+                    descriptor_types.insert(result.0, DescriptorType::Sampler);
                 }
                 Op::OpTypeSampledImage { result, image_type } => {
-                    let t = if let Some(Type::Image2D { .. }) = types.get(&image_type.0) {
+                    // glslang declares `samplerBuffer` as a sampled buffer image, which is bound as a texel buffer
+                    let descriptor_type = match types.get(&image_type.0) {
+                        Some(Type::Image {
+                            dim: ImageDim::Buffer,
+                            ..
+                        }) => DescriptorType::UniformTexelBuffer,
+                        _ => DescriptorType::CombinedImageSampler,
+                    };
+                    descriptor_types.insert(result.0, descriptor_type);
+
+                    let t = if let Some(Type::Image { .. }) = types.get(&image_type.0) {
                         Type::SampledImage {
                             image_type_id: image_type.0,
                         }
@@ -459,17 +1340,29 @@ impl Module {
                     };
                     types.insert(result.0, t);
                 }
+                Op::OpTypeAccelerationStructureKHR { result } => {
+                    types.insert(result.0, Type::AccelerationStructure);
+                    descriptor_types.insert(result.0, DescriptorType::AccelerationStructure);
+                }
+                Op::OpTypeRayQueryKHR { result } => {
+                    types.insert(result.0, Type::RayQuery);
+                }
                 Op::OpTypeArray {
                     result,
                     element_type,
                     length,
                 } => {
-                    if let Some(length) = constants.get(&length.0) {
+                    // the element type has to be declared first, otherwise arrays could contain themselves
+                    if !types.contains_key(&element_type.0) || types.contains_key(&result.0) {
+                        return Err(Error::InvalidId);
+                    }
+                    if let Some(size) = resolve_size(constants, types, length.0, &mut cache) {
                         types.insert(
                             result.0,
                             Type::Array {
                                 element_type_id: element_type.0,
-                                length: Some(*length),
+                                length: ArrayLength::Constant(size),
+                                stride: None,
                             },
                         );
                     } else {
@@ -480,11 +1373,15 @@ impl Module {
                     result,
                     element_type,
                 } => {
+                    if !types.contains_key(&element_type.0) || types.contains_key(&result.0) {
+                        return Err(Error::InvalidId);
+                    }
                     types.insert(
                         result.0,
                         Type::Array {
                             element_type_id: element_type.0,
-                            length: None,
+                            length: ArrayLength::Runtime,
+                            stride: None,
                         },
                     );
                 }
@@ -503,7 +1400,8 @@ impl Module {
                                     type_id: e.0,
                                     offset: None,
                                     row_major: true,
-                                    stride: 16,
+                                    stride: None,
+                                    builtin: None,
                                 })
                                 .collect(),
                         },
@@ -518,12 +1416,30 @@ impl Module {
                         result.0,
                         Type::Pointer {
                             storage_class: match storage_class {
-                                ops::StorageClass::Unknown => StorageClass::Unknown,
-                                ops::StorageClass::UniformConstant {}
-                                | ops::StorageClass::Uniform {} => StorageClass::Uniform,
+                                ops::StorageClass::Unknown(_) => StorageClass::Unknown,
+                                ops::StorageClass::UniformConstant {} => {
+                                    StorageClass::UniformConstant
+                                }
+                                ops::StorageClass::Uniform {} => StorageClass::Uniform,
+                                ops::StorageClass::StorageBuffer {} => StorageClass::StorageBuffer,
                                 ops::StorageClass::PushConstant {} => StorageClass::PushConstant,
                                 ops::StorageClass::Input {} => StorageClass::Input,
                                 ops::StorageClass::Output {} => StorageClass::Output,
+                                ops::StorageClass::CallableDataKHR {} => StorageClass::CallableData,
+                                ops::StorageClass::IncomingCallableDataKHR {} => {
+                                    StorageClass::IncomingCallableData
+                                }
+                                ops::StorageClass::RayPayloadKHR {} => StorageClass::RayPayload,
+                                ops::StorageClass::HitAttributeKHR {} => StorageClass::HitAttribute,
+                                ops::StorageClass::IncomingRayPayloadKHR {} => {
+                                    StorageClass::IncomingRayPayload
+                                }
+                                ops::StorageClass::ShaderRecordBufferKHR {} => {
+                                    StorageClass::ShaderRecordBuffer
+                                }
+                                ops::StorageClass::PhysicalStorageBuffer {} => {
+                                    StorageClass::PhysicalStorageBuffer
+                                }
                             },
                             pointed_type_id: pointed_type.0,
                         },
@@ -533,13 +1449,108 @@ impl Module {
                     result_type,
                     result,
                     value,
+                }
+                | Op::OpSpecConstant {
+                    result_type,
+                    result,
+                    value,
                 } => {
-                    if let Some(Type::UInt32) = types.get(&result_type.0) {
-                        if value.len() == 1 {
-                            constants.insert(result.0, value[0]);
-                        }
+                    let value = types
+                        .get(&result_type.0)
+                        .and_then(|t| ConstantValue::from_words(t, value));
+                    if let Some(value) = value {
+                        constants.insert(
+                            result.0,
+                            RawConstant {
+                                type_id: result_type.0,
+                                kind: ConstantKind::Scalar(value),
+                                specialization: matches!(op, Op::OpSpecConstant { .. }),
+                                spec_id: None,
+                                builtin: None,
+                                name: None,
+                            },
+                        );
                     }
                 }
+                Op::OpConstantTrue {
+                    result_type,
+                    result,
+                }
+                | Op::OpConstantFalse {
+                    result_type,
+                    result,
+                }
+                | Op::OpSpecConstantTrue {
+                    result_type,
+                    result,
+                }
+                | Op::OpSpecConstantFalse {
+                    result_type,
+                    result,
+                } => {
+                    constants.insert(
+                        result.0,
+                        RawConstant {
+                            type_id: result_type.0,
+                            kind: ConstantKind::Scalar(ConstantValue::Bool(matches!(
+                                op,
+                                Op::OpConstantTrue { .. } | Op::OpSpecConstantTrue { .. }
+                            ))),
+                            specialization: matches!(
+                                op,
+                                Op::OpSpecConstantTrue { .. } | Op::OpSpecConstantFalse { .. }
+                            ),
+                            spec_id: None,
+                            builtin: None,
+                            name: None,
+                        },
+                    );
+                }
+                Op::OpConstantComposite {
+                    result_type,
+                    result,
+                    constituents,
+                }
+                | Op::OpSpecConstantComposite {
+                    result_type,
+                    result,
+                    constituents,
+                } => {
+                    constants.insert(
+                        result.0,
+                        RawConstant {
+                            type_id: result_type.0,
+                            kind: ConstantKind::Composite(
+                                constituents.iter().map(|id| id.0).collect(),
+                            ),
+                            specialization: matches!(op, Op::OpSpecConstantComposite { .. }),
+                            spec_id: None,
+                            builtin: None,
+                            name: None,
+                        },
+                    );
+                }
+                Op::OpSpecConstantOp {
+                    result_type,
+                    result,
+                    opcode,
+                    operands,
+                } => {
+                    constants.insert(
+                        result.0,
+                        RawConstant {
+                            type_id: result_type.0,
+                            kind: ConstantKind::Operation {
+                                opcode: *opcode,
+                                operands: operands.iter().map(|id| id.0).collect(),
+                            },
+                            specialization: true,
+                            spec_id: None,
+                            builtin: None,
+                            name: None,
+                        },
+                    );
+                }
                 Op::OpVariable {
                     result_type,
                     result,
@@ -552,6 +1563,14 @@ impl Module {
                             set: None,
                             binding: None,
                             location: None,
+                            builtin: None,
+                            input_attachment_index: None,
+                            component: None,
+                            interpolation: Interpolation::Smooth,
+                            sampling: Sampling::Center,
+                            patch: false,
+                            per_primitive: false,
+                            index: None,
                             type_id: result_type.0,
                             name: None,
                         },
@@ -559,22 +1578,46 @@ impl Module {
                 }
                 Op::OpEntryPoint {
                     execution_model,
-                    func: _,
+                    func,
                     name,
                     interface,
                 } => {
                     entries.push(RawEntryPoint {
+                        func: func.0,
                         name: name.clone(),
                         execution_model: match execution_model {
-                            ops::ExecutionModel::Unknown => {
-                                return Err(Error::Other(
-                                    "Unknown execution model in entry point".to_string(),
-                                ))
-                            }
+                            ops::ExecutionModel::Unknown(code) => ExecutionModel::Unknown(*code),
                             ops::ExecutionModel::Vertex {} => ExecutionModel::Vertex,
+                            ops::ExecutionModel::TessellationControl {} => {
+                                ExecutionModel::TessellationControl
+                            }
+                            ops::ExecutionModel::TessellationEvaluation {} => {
+                                ExecutionModel::TessellationEvaluation
+                            }
+                            ops::ExecutionModel::Geometry {} => ExecutionModel::Geometry,
                             ops::ExecutionModel::Fragment {} => ExecutionModel::Fragment,
+                            ops::ExecutionModel::GLCompute {} => ExecutionModel::GLCompute,
+                            ops::ExecutionModel::Kernel {} => ExecutionModel::Kernel,
+                            ops::ExecutionModel::TaskNV {} => ExecutionModel::TaskNV,
+                            ops::ExecutionModel::MeshNV {} => ExecutionModel::MeshNV,
+                            ops::ExecutionModel::RayGenerationKHR {} => {
+                                ExecutionModel::RayGeneration
+                            }
+                            ops::ExecutionModel::IntersectionKHR {} => ExecutionModel::Intersection,
+                            ops::ExecutionModel::AnyHitKHR {} => ExecutionModel::AnyHit,
+                            ops::ExecutionModel::ClosestHitKHR {} => ExecutionModel::ClosestHit,
+                            ops::ExecutionModel::MissKHR {} => ExecutionModel::Miss,
+                            ops::ExecutionModel::CallableKHR {} => ExecutionModel::Callable,
+                            ops::ExecutionModel::TaskEXT {} => ExecutionModel::TaskEXT,
+                            ops::ExecutionModel::MeshEXT {} => ExecutionModel::MeshEXT,
                         },
                         interface: interface.clone(),
+                        local_size: None,
+                        local_size_id: None,
+                        uses_ray_query: false,
+                        fragment_modes: FragmentExecutionModes::default(),
+                        geometry_modes: GeometryExecutionModes::default(),
+                        tessellation_modes: TessellationExecutionModes::default(),
                     });
                 }
                 _ => {}
@@ -588,7 +1631,7 @@ impl Module {
 /// Represents a type declared in a SPIRV module.
 ///
 /// Types are declared in a hierarchy, with e.g. pointers relying on previously declared types as pointed-to types.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[non_exhaustive]
 pub enum Type {
     /// An unsupported type
@@ -597,44 +1640,70 @@ pub enum Type {
     Void,
     /// A boolean
     Bool,
-    /// A signed 32-Bit integer
-    Int32,
-    /// An unsigned 32-Bit integer
-    UInt32,
-    /// A 32-Bit float
-    Float32,
-    /// A 2 component, 32-Bit vector (GLSL: vec2)
-    Vec2,
-    /// A 3 component, 32-Bit vector (GLSL: vec3)
-    Vec3,
-    /// A 4 component, 32-Bit vector (GLSL: vec4)
-    Vec4,
-    /// A 3x3, 32-Bit Matrix (GLSL: mat3)
-    Mat3,
-    /// A 4x4, 32-Bit Matrix (GLSL: mat4)
-    Mat4,
-    /// A 2D image
-    Image2D {
-        /// true if this image is a depth image
-        depth: bool,
-        /// true if this image can be sampled from
-        sampled: bool,
-        /// SPIRV code of the images format (should always be 0 in Vulkan)
-        format: u32,
+    /// An integer (GLSL: `int`, `uint`, `int64_t`, `uint16_t`, etc.)
+    Int {
+        /// The width of the integer in bits
+        width: u32,
+        /// true if the integer is signed
+        signed: bool,
+    },
+    /// A floating point number (GLSL: `float`, `double`, `float16_t`)
+    Float {
+        /// The width of the float in bits
+        width: u32,
+    },
+    /// A vector of 2 to 4 scalars (GLSL: `vec3`, `uvec4`, `bvec2`, `f16vec4`, etc.)
+    Vector {
+        /// type id of the scalar components
+        component_type_id: u32,
+        /// The number of components
+        count: u32,
+    },
+    /// A matrix of 2 to 4 column vectors (GLSL: `mat4`, `mat2x3`, `dmat4`, etc.)
+    Matrix {
+        /// type id of the [`Type::Vector`] of each column, its component count is the number of rows
+        column_type_id: u32,
+        /// The number of columns
+        columns: u32,
+    },
+    /// An image (GLSL: `texture2D`, `image3D`, `subpassInput`, etc.)
+    Image {
+        /// type id of the scalar components returned by sampling or reading the image (e.g. an unsigned integer for `utexture2D`)
+        sampled_type_id: u32,
+        /// The dimensionality of the image
+        dim: ImageDim,
+        /// Whether the image is a depth image
+        depth: ImageDepth,
+        /// true if the image is an array of layers (GLSL: `texture2DArray`, etc.)
+        arrayed: bool,
+        /// true if the image is multisampled (GLSL: `texture2DMS`, etc.)
+        multisampled: bool,
+        /// Whether the image is used with or without a sampler
+        sampled: ImageUsage,
+        /// The texel format of a storage image, [`None`] if it is unknown (e.g. sampled images and `shaderStorageImageReadWithoutFormat`)
+        format: Option<ImageFormat>,
+        /// The access qualifier of the image (only used in `OpenCL` kernels)
+        access: Option<AccessQualifier>,
     },
     /// An opaque sampler object
     Sampler,
-    /// A combined image and sampler (Vulkan: CombinedImageSampler descriptor)
+    /// An opaque acceleration structure (GLSL: `accelerationStructureEXT`)
+    AccelerationStructure,
+    /// An opaque ray query object (GLSL: `rayQueryEXT`)
+    RayQuery,
+    /// A combined image and sampler (Vulkan: `CombinedImageSampler` descriptor)
     SampledImage {
-        /// type id of the image contained in the SampledImage
+        /// type id of the image contained in the `SampledImage`
         image_type_id: u32,
     },
-    /// Either a static array with known length (`length` is [`Some`]) or dynamic array with unknown length (`length` is [`None`])
+    /// Either a static array with known length or a runtime array with unknown length
     Array {
         /// type id of the contained type
         element_type_id: u32,
-        /// length of the array (if known)
-        length: Option<u32>,
+        /// length of the array
+        length: ArrayLength,
+        /// stride in bytes between two elements of the array (if known)
+        stride: Option<u32>,
     },
     /// A struct containing other types
     Struct {
@@ -651,8 +1720,28 @@ pub enum Type {
     },
 }
 
+/// The length of a [`Type::Array`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ArrayLength {
+    /// The array has a length given by a constant, which may depend on specialization constants
+    Constant(ConstantSize),
+    /// The array is a runtime array whose length is only known at runtime
+    Runtime,
+}
+
+impl ArrayLength {
+    /// Returns the length of the array if no constant is specialized, or [`None`] if it is not known
+    pub fn value(self) -> Option<u32> {
+        match self {
+            ArrayLength::Constant(size) => size.value(),
+            ArrayLength::Runtime => None,
+        }
+    }
+}
+
 /// Describes a single member of a [`Type::Struct`] type
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct StructMember {
     /// The name of the member variable (if known)
     pub name: Option<String>,
@@ -662,12 +1751,14 @@ pub struct StructMember {
     pub offset: Option<u32>,
     /// For matrix members: whether this matrix is stored in row major order
     pub row_major: bool,
-    /// For matrix members: The stride between rows/columns of the matrix
-    pub stride: u32,
+    /// For matrix members: The stride between rows/columns of the matrix (if known)
+    pub stride: Option<u32>,
+    /// For members of I/O blocks: the built-in variable this member represents (if any)
+    pub builtin: Option<BuiltIn>,
 }
 
 /// Describes what type of storage a pointer points to
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum StorageClass {
     Unknown,
@@ -681,16 +1772,109 @@ pub enum StorageClass {
     Input,
     /// The pointer is an output variable
     Output,
+    /// The pointer is a storage buffer variable (SSBOs since SPIR-V 1.3)
+    StorageBuffer,
+    /// The pointer is callable data passed to a callable shader (GLSL: `callableDataEXT`)
+    CallableData,
+    /// The pointer is callable data received by a callable shader (GLSL: `callableDataInEXT`)
+    IncomingCallableData,
+    /// The pointer is a ray payload passed to `traceRayEXT` (GLSL: `rayPayloadEXT`)
+    RayPayload,
+    /// The pointer is a hit attribute written by an intersection shader (GLSL: `hitAttributeEXT`)
+    HitAttribute,
+    /// The pointer is a ray payload received by a hit or miss shader (GLSL: `rayPayloadInEXT`)
+    IncomingRayPayload,
+    /// The pointer is the shader record of the shader binding table (GLSL: `shaderRecordEXT`)
+    ShaderRecordBuffer,
+    /// The pointer is a 64-bit device address of a buffer (GLSL: `buffer_reference`)
+    PhysicalStorageBuffer,
 }
 
 /// The execution model of an [`EntryPoint`].
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum ExecutionModel {
     /// A Vertex Shader
     Vertex,
+    /// A Tessellation Control Shader (HLSL: Hull Shader)
+    TessellationControl,
+    /// A Tessellation Evaluation Shader (HLSL: Domain Shader)
+    TessellationEvaluation,
+    /// A Geometry Shader
+    Geometry,
     /// A Fragment Shader
     Fragment,
+    /// A Vulkan/GLSL Compute Shader
+    GLCompute,
+    /// An `OpenCL` Kernel
+    Kernel,
+    /// A Task Shader (`NV_mesh_shader`)
+    TaskNV,
+    /// A Mesh Shader (`NV_mesh_shader`)
+    MeshNV,
+    /// A Ray Generation Shader
+    RayGeneration,
+    /// A Ray Intersection Shader
+    Intersection,
+    /// A Ray Any-Hit Shader
+    AnyHit,
+    /// A Ray Closest-Hit Shader
+    ClosestHit,
+    /// A Ray Miss Shader
+    Miss,
+    /// A Callable Shader
+    Callable,
+    /// A Task Shader (`EXT_mesh_shader`)
+    TaskEXT,
+    /// A Mesh Shader (`EXT_mesh_shader`)
+    MeshEXT,
+    /// An execution model not known to this library, contains the raw SPIR-V code
+    Unknown(u32),
+}
+
+/// The Vulkan descriptor type of a [`UniformVariable`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DescriptorType {
+    /// An opaque sampler object (GLSL: `sampler`)
+    Sampler,
+    /// A combined image and sampler (GLSL: `sampler2D`, etc.)
+    CombinedImageSampler,
+    /// An image that can be sampled from (GLSL: `texture2D`, etc.)
+    SampledImage,
+    /// An image that can be read and written (GLSL: `image2D`, etc.)
+    StorageImage,
+    /// A read-only buffer accessed through a texture unit (GLSL: `samplerBuffer`)
+    UniformTexelBuffer,
+    /// A read-write buffer accessed as a storage image (GLSL: `imageBuffer`)
+    StorageTexelBuffer,
+    /// A uniform buffer (GLSL: `uniform` block)
+    UniformBuffer,
+    /// A storage buffer (GLSL: `buffer` block)
+    StorageBuffer,
+    /// An input attachment of the current subpass (GLSL: `subpassInput`)
+    InputAttachment,
+    /// A ray tracing acceleration structure (GLSL: `accelerationStructureEXT`)
+    AccelerationStructure,
+}
+
+/// The number of descriptors bound by a [`UniformVariable`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DescriptorCount {
+    /// A fixed number of descriptors (1 for non-array variables)
+    Fixed(u32),
+    /// A runtime sized array of descriptors (GLSL: `texture2D textures[]`)
+    Runtime,
+    /// A fixed number of descriptors, given by a specialization constant expression that cannot be evaluated
+    Unknown,
+    /// A fixed number of descriptors that does not fit into a `u32` (GLSL: `sampler s[65536][65536]`)
+    Overflow,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BlockDecoration {
+    Block,
+    BufferBlock,
 }
 
 #[derive(Debug, Clone)]
@@ -698,28 +1882,66 @@ struct RawVariable {
     set: Option<u32>,
     binding: Option<u32>,
     location: Option<u32>,
+    builtin: Option<BuiltIn>,
+    input_attachment_index: Option<u32>,
+    component: Option<u32>,
+    interpolation: Interpolation,
+    sampling: Sampling,
+    patch: bool,
+    per_primitive: bool,
+    index: Option<u32>,
     type_id: u32,
     name: Option<String>,
 }
 
 #[derive(Debug)]
 struct RawEntryPoint {
+    func: u32,
     name: String,
     execution_model: ExecutionModel,
     interface: Vec<Id>,
+    local_size: Option<[u32; 3]>,
+    local_size_id: Option<[u32; 3]>,
+    uses_ray_query: bool,
+    fragment_modes: FragmentExecutionModes,
+    geometry_modes: GeometryExecutionModes,
+    tessellation_modes: TessellationExecutionModes,
 }
 
 /// Describes a uniform variable declared in a SPIRV module
 #[derive(Debug, Clone)]
 pub struct UniformVariable {
-    /// Which DescriptorSet the variable is contained in (if known)
+    /// Which `DescriptorSet` the variable is contained in (if known)
     pub set: u32,
-    /// Which DescriptorSet binding the variable is contained in (if known)
+    /// Which `DescriptorSet` binding the variable is contained in (if known)
     pub binding: u32,
     /// The type id of the variable's [`Type`]
     pub type_id: u32,
     /// The variables name (if known)
     pub name: Option<String>,
+    /// The [`DescriptorType`] of the variable
+    pub descriptor_type: DescriptorType,
+    /// The number of descriptors bound by the variable, greater than 1 for arrays of resources
+    pub descriptor_count: DescriptorCount,
+}
+
+/// Describes an input attachment (GLSL: `subpassInput`) declared in a SPIRV module
+#[derive(Debug, Clone)]
+pub struct InputAttachment {
+    /// The index of the attachment in the subpass' input attachments (e.g. GLSL `layout(input_attachment_index=XXX)`).
+    ///
+    /// Arrays of input attachments use consecutive indices starting at this one.
+    pub attachment_index: u32,
+    /// Which `DescriptorSet` the variable is contained in
+    pub set: u32,
+    /// Which `DescriptorSet` binding the variable is contained in
+    pub binding: u32,
+    /// The type id of the variable's [`Type`]
+    pub type_id: u32,
+    /// The variables name (if known)
+    pub name: Option<String>,
+    /// true if the attachment is multisampled (GLSL: `subpassInputMS`)
+    pub multisampled: bool,
 }
 
 /// Describes a push constant variable declared in a SPIRV module
@@ -736,12 +1958,72 @@ pub struct PushConstantVariable {
 pub struct LocationVariable {
     /// The location of the variable (e.g. GLSL `layout(location=XXX)`)
     pub location: u32,
+    /// The first component of the location used by the variable (e.g. GLSL `layout(component=XXX)`), if declared
+    pub component: Option<u32>,
+    /// How the variable is interpolated between the vertices of a primitive
+    pub interpolation: Interpolation,
+    /// Where within a pixel the variable is interpolated
+    pub sampling: Sampling,
+    /// true if the variable is passed per patch instead of per vertex between tessellation stages (GLSL: `patch`)
+    pub patch: bool,
+    /// true if the variable is passed per primitive instead of per vertex from a mesh shader (GLSL: `perprimitiveEXT`)
+    pub per_primitive: bool,
+    /// The blend equation input of a fragment output used for dual-source blending (e.g. GLSL `layout(index=XXX)`)
+    pub index: Option<u32>,
+    /// The type id of the variable's [`Type`]
+    pub type_id: u32,
+    /// The variable's name (if known)
+    pub name: Option<String>,
+}
+
+/// How a [`LocationVariable`] is interpolated between the vertices of a primitive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interpolation {
+    /// Perspective correct interpolation (the default)
+    Smooth,
+    /// No interpolation, the value of the provoking vertex is used (GLSL: `flat`)
+    Flat,
+    /// Linear interpolation in screen space (GLSL: `noperspective`)
+    NoPerspective,
+}
+
+/// Where within a pixel a [`LocationVariable`] is interpolated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sampling {
+    /// At the pixel center (the default)
+    Center,
+    /// At the centroid of the covered samples (GLSL: `centroid`)
+    Centroid,
+    /// At each sample, which runs the fragment shader per sample (GLSL: `sample`)
+    Sample,
+}
+
+/// Describes a variable used to pass data between ray tracing shaders, see [`EntryPoint::ray_tracing_variables`]
+#[derive(Debug, Clone)]
+pub struct RayTracingVariable {
+    /// The [`StorageClass`] of the variable, which determines how it is passed between shaders
+    pub storage_class: StorageClass,
+    /// The location of ray payloads and callable data passed to other shaders (e.g. GLSL `layout(location=XXX)`)
+    pub location: Option<u32>,
     /// The type id of the variable's [`Type`]
     pub type_id: u32,
     /// The variable's name (if known)
     pub name: Option<String>,
 }
 
+/// Describes a built-in input or output variable (e.g. GLSL `gl_Position`) used in a shader
+#[derive(Debug, Clone)]
+pub struct BuiltInVariable {
+    /// The [`BuiltIn`] represented by the variable
+    pub builtin: BuiltIn,
+    /// The type id of the built-in's [`Type`]
+    pub type_id: u32,
+    /// The name of the variable or block member (if known)
+    pub name: Option<String>,
+    /// The index of the member within its I/O block (e.g. `gl_PerVertex`), [`None`] if the built-in is a variable itself
+    pub member: Option<u32>,
+}
+
 mod private {
     pub trait Variable {
         fn get_type_id(&self) -> u32;
@@ -766,3 +2048,222 @@ impl private::Variable for LocationVariable {
         self.type_id
     }
 }
+impl private::Variable for InputAttachment {
+    fn get_type_id(&self) -> u32 {
+        self.type_id
+    }
+}
+impl private::Variable for RayTracingVariable {
+    fn get_type_id(&self) -> u32 {
+        self.type_id
+    }
+}
+impl private::Variable for BuiltInVariable {
+    fn get_type_id(&self) -> u32 {
+        self.type_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        test_utils::Assembler, BuiltIn, BuiltInVariable, ConstantValue, DescriptorCount,
+        DescriptorType, Module,
+    };
+
+    // declares `sampler s[outer][inner]` at set 0, binding 0, used by a fragment shader
+    fn sampler_array(outer: u32, inner: u32) -> Assembler {
+        let mut asm = Assembler::new();
+        asm.entry_point(4, 100, &[7])
+            .op(71, &[7, 34, 0])
+            .op(71, &[7, 33, 0])
+            .op(21, &[1, 32, 0])
+            .op(43, &[1, 2, inner])
+            .op(43, &[1, 3, outer])
+            .op(26, &[4])
+            .op(28, &[5, 4, 2])
+            .op(28, &[6, 5, 3])
+            .op(32, &[8, 0, 6])
+            .op(59, &[8, 7, 0]);
+        asm
+    }
+
+    #[test]
+    fn arrays_of_arrays_are_flattened() {
+        let module = Module::from_words(sampler_array(4, 3).words()).unwrap();
+        assert_eq!(
+            module.get_entry_points()[0].uniforms[0].descriptor_count,
+            DescriptorCount::Fixed(12)
+        );
+    }
+
+    #[test]
+    fn overflowing_descriptor_count_is_reported() {
+        let module = Module::from_words(sampler_array(65536, 65536).words()).unwrap();
+        assert_eq!(
+            module.get_entry_points()[0].uniforms[0].descriptor_count,
+            DescriptorCount::Overflow
+        );
+    }
+
+    #[test]
+    fn specialization_updates_descriptor_count() {
+        // `layout(constant_id = 0) const uint N = 1; sampler s[N][65536]`
+        let mut asm = Assembler::new();
+        asm.entry_point(4, 100, &[7])
+            .op(71, &[7, 34, 0])
+            .op(71, &[7, 33, 0])
+            .op(71, &[3, 1, 0])
+            .op(21, &[1, 32, 0])
+            .op(43, &[1, 2, 65536])
+            .op(50, &[1, 3, 1])
+            .op(26, &[4])
+            .op(28, &[5, 4, 2])
+            .op(28, &[6, 5, 3])
+            .op(32, &[8, 0, 6])
+            .op(59, &[8, 7, 0]);
+        let module = Module::from_words(asm.words()).unwrap();
+        let count = |module: &Module| module.get_entry_points()[0].uniforms[0].descriptor_count;
+        assert_eq!(count(&module), DescriptorCount::Fixed(65536));
+
+        let specialized = module.specialize(&HashMap::from([(0, ConstantValue::UInt(2))]));
+        assert_eq!(count(&specialized), DescriptorCount::Fixed(131_072));
+        let specialized = module.specialize(&HashMap::from([(0, ConstantValue::UInt(65536))]));
+        assert_eq!(count(&specialized), DescriptorCount::Overflow);
+    }
+
+    #[test]
+    fn arrays_of_undeclared_types_are_rejected() {
+        let mut asm = Assembler::new();
+        // `%5 = OpTypeArray %5 %len`
+        asm.op(21, &[1, 32, 0])
+            .op(43, &[1, 2, 4])
+            .op(28, &[5, 5, 2]);
+        assert!(Module::from_words(asm.words()).is_err());
+
+        let mut asm = Assembler::new();
+        // `%5 = OpTypeRuntimeArray %5`
+        asm.op(29, &[5, 5]);
+        assert!(Module::from_words(asm.words()).is_err());
+    }
+
+    #[test]
+    fn sampled_buffer_images_are_texel_buffers() {
+        // `samplerBuffer` and `sampler2D` at set 0, bindings 0 and 1
+        let mut asm = Assembler::new();
+        asm.entry_point(4, 100, &[20, 21])
+            .op(71, &[20, 34, 0])
+            .op(71, &[20, 33, 0])
+            .op(71, &[21, 34, 0])
+            .op(71, &[21, 33, 1])
+            .op(22, &[1, 32])
+            .op(25, &[2, 1, 5, 0, 0, 0, 1, 0])
+            .op(25, &[3, 1, 1, 0, 0, 0, 1, 0])
+            .op(27, &[4, 2])
+            .op(27, &[5, 3])
+            .op(32, &[6, 0, 4])
+            .op(32, &[7, 0, 5])
+            .op(59, &[6, 20, 0])
+            .op(59, &[7, 21, 0]);
+        let module = Module::from_words(asm.words()).unwrap();
+
+        let mut uniforms: Vec<_> = module.get_entry_points()[0]
+            .uniforms
+            .iter()
+            .map(|u| (u.binding, u.descriptor_type))
+            .collect();
+        uniforms.sort_by_key(|(binding, _)| *binding);
+        assert_eq!(
+            uniforms,
+            [
+                (0, DescriptorType::UniformTexelBuffer),
+                (1, DescriptorType::CombinedImageSampler)
+            ]
+        );
+    }
+
+    #[test]
+    fn unevaluable_array_length_is_not_a_runtime_array() {
+        // `sampler s[N / 0]` where `N` is a specialization constant
+        let mut asm = Assembler::new();
+        asm.entry_point(4, 100, &[7])
+            .op(71, &[7, 34, 0])
+            .op(71, &[7, 33, 0])
+            .op(21, &[1, 32, 0])
+            .op(50, &[1, 2, 4])
+            .op(43, &[1, 3, 0])
+            .op(52, &[1, 9, 134, 2, 3])
+            .op(26, &[4])
+            .op(28, &[5, 4, 9])
+            .op(32, &[8, 0, 5])
+            .op(59, &[8, 7, 0]);
+        let module = Module::from_words(asm.words()).unwrap();
+
+        assert_eq!(
+            module.get_entry_points()[0].uniforms[0].descriptor_count,
+            DescriptorCount::Unknown
+        );
+    }
+
+    #[test]
+    fn io_block_members_are_builtins() {
+        // a geometry shader reading `gl_in[3]` and `gl_PrimitiveID` and writing `gl_PerVertex`
+        let mut asm = Assembler::new();
+        asm.entry_point(3, 100, &[20, 22, 21])
+            .op_str(5, &[22], "gl_PrimitiveID", &[])
+            .op_str(6, &[3, 0], "gl_Position", &[])
+            .op_str(6, &[3, 1], "gl_PointSize", &[])
+            .op(72, &[3, 0, 11, 0])
+            .op(72, &[3, 1, 11, 1])
+            .op(71, &[3, 2])
+            .op(71, &[22, 11, 7])
+            .op(22, &[1, 32])
+            .op(23, &[2, 1, 4])
+            .op(30, &[3, 2, 1])
+            .op(21, &[4, 32, 0])
+            .op(43, &[4, 5, 3])
+            .op(28, &[6, 3, 5])
+            .op(32, &[7, 1, 6])
+            .op(32, &[8, 3, 3])
+            .op(21, &[9, 32, 1])
+            .op(32, &[10, 1, 9])
+            .op(59, &[7, 20, 1])
+            .op(59, &[8, 21, 3])
+            .op(59, &[10, 22, 1]);
+        let module = Module::from_words(asm.words()).unwrap();
+        let entry_point = &module.get_entry_points()[0];
+
+        let builtins = |vars: &[BuiltInVariable]| -> Vec<_> {
+            vars.iter()
+                .map(|v| (v.builtin, v.type_id, v.name.clone(), v.member))
+                .collect()
+        };
+        let members = [
+            (
+                BuiltIn::Position,
+                2,
+                Some("gl_Position".to_owned()),
+                Some(0),
+            ),
+            (
+                BuiltIn::PointSize,
+                1,
+                Some("gl_PointSize".to_owned()),
+                Some(1),
+            ),
+        ];
+        let mut inputs = members.to_vec();
+        inputs.push((
+            BuiltIn::PrimitiveId,
+            9,
+            Some("gl_PrimitiveID".to_owned()),
+            None,
+        ));
+
+        assert_eq!(builtins(&entry_point.builtin_inputs), inputs);
+        assert_eq!(builtins(&entry_point.builtin_outputs), members);
+        assert!(entry_point.inputs.is_empty() && entry_point.outputs.is_empty());
+    }
+}
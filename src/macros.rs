@@ -0,0 +1,36 @@
+/// Used to more easily declare public enums mirroring SPIRV enumerants
+macro_rules! spirv_enum {
+    (
+        $(#[$meta:meta])*
+        $enum_name:ident {
+            $($(#[$variant_meta:meta])* $variant_code:literal = $variant_name:ident),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum $enum_name {
+            $($(#[$variant_meta])* $variant_name,)*
+            /// A value not known to this library, contains the raw SPIR-V code
+            Unknown(u32),
+        }
+
+        impl $enum_name {
+            #[allow(dead_code)]
+            pub(crate) fn from_code(code: u32) -> Self {
+                match code {
+                    $($variant_code => Self::$variant_name,)*
+                    _ => Self::Unknown(code),
+                }
+            }
+
+            /// Returns the raw SPIR-V code of the value
+            pub fn code(self) -> u32 {
+                match self {
+                    $(Self::$variant_name => $variant_code,)*
+                    Self::Unknown(code) => code,
+                }
+            }
+        }
+    };
+}
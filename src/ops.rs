@@ -50,6 +50,9 @@ macro_rules! ops {
 
 ops!(
     5 = OpName(target: Id, name: String),
+    10 = OpExtension(name: String),
+    11 = OpExtInstImport(result: Id, name: String),
+    14 = OpMemoryModel(addressing_model: u32, memory_model: u32),
     6 = OpMemberName(target: Id, member_index: u32, name: String),
     15 = OpEntryPoint(
         execution_model: ExecutionModel,
@@ -57,6 +60,8 @@ ops!(
         name: String,
         interface: Vec<Id>
     ),
+    16 = OpExecutionMode(entry_point: Id, mode: ExecutionMode),
+    17 = OpCapability(capability: u32),
     71 = OpDecorate(target: Id, decoration: Decoration),
     72 = OpMemberDecorate(target: Id, member_index: u32, decoration: Decoration),
     19 = OpTypeVoid(result: Id),
@@ -82,13 +87,28 @@ ops!(
     29 = OpTypeRuntimeArray(result: Id, element_type: Id),
     30 = OpTypeStruct(result: Id, element_types: Vec<Id>),
     32 = OpTypePointer(result: Id, storage_class: StorageClass, pointed_type: Id),
+    41 = OpConstantTrue(result_type: Id, result: Id),
+    42 = OpConstantFalse(result_type: Id, result: Id),
     43 = OpConstant(result_type: Id, result: Id, value: Vec<u32>),
+    44 = OpConstantComposite(result_type: Id, result: Id, constituents: Vec<Id>),
+    48 = OpSpecConstantTrue(result_type: Id, result: Id),
+    49 = OpSpecConstantFalse(result_type: Id, result: Id),
+    50 = OpSpecConstant(result_type: Id, result: Id, value: Vec<u32>),
+    51 = OpSpecConstantComposite(result_type: Id, result: Id, constituents: Vec<Id>),
+    52 = OpSpecConstantOp(result_type: Id, result: Id, opcode: u32, operands: Vec<Id>),
+    5341 = OpTypeAccelerationStructureKHR(result: Id),
+    4472 = OpTypeRayQueryKHR(result: Id),
+    54 = OpFunction(result_type: Id, result: Id, control: u32, function_type: Id),
+    56 = OpFunctionEnd(),
+    57 = OpFunctionCall(result_type: Id, result: Id, function: Id, arguments: Vec<Id>),
+    4473 = OpRayQueryInitializeKHR(ray_query: Id),
     59 = OpVariable(
         result_type: Id,
         result: Id,
         storage_class: StorageClass,
         initializer: Option<Id>
     ),
+    331 = OpExecutionModeId(entry_point: Id, mode: ExecutionMode),
 );
 
 trait DecodeArg {
@@ -133,7 +153,7 @@ impl DecodeArg for String {
 
         if stream.iter().any(|e| {
             num_words += 1;
-            e.to_le_bytes().iter().any(|b| *b == 0)
+            e.to_le_bytes().contains(&0)
         }) {
             let arg = unsafe { CStr::from_ptr(stream.as_ptr().cast::<i8>()) }
                 .to_str()?
@@ -183,8 +203,9 @@ macro_rules! enums {
     ),* $(,)?) => {
         $(
             #[derive(Debug)]
+            #[allow(dead_code)]
             pub(crate) enum $enum_name {
-                Unknown,
+                Unknown(u32),
                 $($variant_name { $($arg_name: $arg_type),* }),*
             }
 
@@ -207,7 +228,7 @@ macro_rules! enums {
                             }
                         ),*
 
-                        _ => Ok(Self::Unknown),
+                        _ => Ok(Self::Unknown(code)),
                     }
                 }
             }
@@ -217,13 +238,27 @@ macro_rules! enums {
 
 enums!(
     Decoration {
+        1 = SpecId(spec_id: u32),
+        2 = Block(),
+        3 = BufferBlock(),
         4 = RowMajor(),
         5 = ColMajor(),
+        6 = ArrayStride(stride: u32),
         7 = MatrixStride(stride: u32),
+        11 = BuiltIn(builtin: u32),
+        13 = NoPerspective(),
+        14 = Flat(),
+        15 = Patch(),
+        16 = Centroid(),
+        17 = Sample(),
         30 = Location(loc: u32),
+        32 = Index(index: u32),
         33 = Binding(binding: u32),
         34 = DescriptorSet(set: u32),
         35 = Offset(offset: u32),
+        43 = InputAttachmentIndex(index: u32),
+        44 = Component(component: u32),
+        5271 = PerPrimitiveEXT(),
     },
 
     Dim {
@@ -242,10 +277,62 @@ enums!(
         2 = Uniform(),
         3 = Output(),
         9 = PushConstant(),
+        12 = StorageBuffer(),
+        5328 = CallableDataKHR(),
+        5329 = IncomingCallableDataKHR(),
+        5338 = RayPayloadKHR(),
+        5339 = HitAttributeKHR(),
+        5342 = IncomingRayPayloadKHR(),
+        5343 = ShaderRecordBufferKHR(),
+        5349 = PhysicalStorageBuffer(),
+    },
+
+    ExecutionMode {
+        0 = Invocations(count: u32),
+        1 = SpacingEqual(),
+        2 = SpacingFractionalEven(),
+        3 = SpacingFractionalOdd(),
+        4 = VertexOrderCw(),
+        5 = VertexOrderCcw(),
+        7 = OriginUpperLeft(),
+        9 = EarlyFragmentTests(),
+        10 = PointMode(),
+        12 = DepthReplacing(),
+        14 = DepthGreater(),
+        15 = DepthLess(),
+        16 = DepthUnchanged(),
+        17 = LocalSize(x: u32, y: u32, z: u32),
+        19 = InputPoints(),
+        20 = InputLines(),
+        21 = InputLinesAdjacency(),
+        22 = Triangles(),
+        23 = InputTrianglesAdjacency(),
+        24 = Quads(),
+        25 = Isolines(),
+        26 = OutputVertices(count: u32),
+        27 = OutputPoints(),
+        28 = OutputLineStrip(),
+        29 = OutputTriangleStrip(),
+        38 = LocalSizeId(x: Id, y: Id, z: Id),
     },
 
     ExecutionModel {
         0 = Vertex(),
+        1 = TessellationControl(),
+        2 = TessellationEvaluation(),
+        3 = Geometry(),
         4 = Fragment(),
+        5 = GLCompute(),
+        6 = Kernel(),
+        5267 = TaskNV(),
+        5268 = MeshNV(),
+        5313 = RayGenerationKHR(),
+        5314 = IntersectionKHR(),
+        5315 = AnyHitKHR(),
+        5316 = ClosestHitKHR(),
+        5317 = MissKHR(),
+        5318 = CallableKHR(),
+        5364 = TaskEXT(),
+        5365 = MeshEXT(),
     },
 );
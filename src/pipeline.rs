@@ -0,0 +1,435 @@
+use std::{
+    collections::BTreeMap,
+    ops::{BitOr, BitOrAssign},
+};
+
+use thiserror::Error;
+
+use crate::{DescriptorCount, DescriptorType, EntryPoint, ExecutionModel, Module, Type};
+
+/// A set of shader stages, using the bit values of Vulkan's `VkShaderStageFlagBits`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ShaderStages(u32);
+
+impl ShaderStages {
+    pub const VERTEX: Self = Self(0x1);
+    pub const TESSELLATION_CONTROL: Self = Self(0x2);
+    pub const TESSELLATION_EVALUATION: Self = Self(0x4);
+    pub const GEOMETRY: Self = Self(0x8);
+    pub const FRAGMENT: Self = Self(0x10);
+    pub const COMPUTE: Self = Self(0x20);
+    pub const TASK: Self = Self(0x40);
+    pub const MESH: Self = Self(0x80);
+    pub const RAYGEN: Self = Self(0x100);
+    pub const ANY_HIT: Self = Self(0x200);
+    pub const CLOSEST_HIT: Self = Self(0x400);
+    pub const MISS: Self = Self(0x800);
+    pub const INTERSECTION: Self = Self(0x1000);
+    pub const CALLABLE: Self = Self(0x2000);
+
+    /// Returns an empty set of stages
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Returns the raw `VkShaderStageFlags` value
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Returns true if no stage is contained in the set
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns true if all stages in `other` are also contained in `self`
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for ShaderStages {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for ShaderStages {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl From<ExecutionModel> for ShaderStages {
+    fn from(model: ExecutionModel) -> Self {
+        match model {
+            ExecutionModel::Vertex => Self::VERTEX,
+            ExecutionModel::TessellationControl => Self::TESSELLATION_CONTROL,
+            ExecutionModel::TessellationEvaluation => Self::TESSELLATION_EVALUATION,
+            ExecutionModel::Geometry => Self::GEOMETRY,
+            ExecutionModel::Fragment => Self::FRAGMENT,
+            ExecutionModel::GLCompute => Self::COMPUTE,
+            ExecutionModel::TaskNV | ExecutionModel::TaskEXT => Self::TASK,
+            ExecutionModel::MeshNV | ExecutionModel::MeshEXT => Self::MESH,
+            ExecutionModel::RayGeneration => Self::RAYGEN,
+            ExecutionModel::Intersection => Self::INTERSECTION,
+            ExecutionModel::AnyHit => Self::ANY_HIT,
+            ExecutionModel::ClosestHit => Self::CLOSEST_HIT,
+            ExecutionModel::Miss => Self::MISS,
+            ExecutionModel::Callable => Self::CALLABLE,
+            ExecutionModel::Kernel | ExecutionModel::Unknown(_) => Self::empty(),
+        }
+    }
+}
+
+/// Errors that can occur while merging the resources of multiple shader stages
+#[derive(Debug, Clone, Error)]
+pub enum PipelineLayoutError {
+    #[error("set {set}, binding {binding} is declared as both {first:?} and {second:?}")]
+    DescriptorTypeMismatch {
+        set: u32,
+        binding: u32,
+        first: DescriptorType,
+        second: DescriptorType,
+    },
+    #[error(
+        "set {set}, binding {binding} is declared with descriptor counts {first:?} and {second:?}"
+    )]
+    DescriptorCountMismatch {
+        set: u32,
+        binding: u32,
+        first: DescriptorCount,
+        second: DescriptorCount,
+    },
+    #[error("the descriptor count of set {set}, binding {binding} cannot be determined")]
+    UnknownDescriptorCount { set: u32, binding: u32 },
+    #[error("set {set}, binding {binding} is declared with incompatible block layouts")]
+    BlockLayoutMismatch { set: u32, binding: u32 },
+    #[error("the size of the push constants in entry point {entry_point} is unknown")]
+    UnknownPushConstantSize { entry_point: String },
+}
+
+/// A single binding of a [`DescriptorSetLayout`]
+#[derive(Debug, Clone)]
+pub struct DescriptorBinding {
+    /// The binding number within the set
+    pub binding: u32,
+    /// The [`DescriptorType`] of the binding
+    pub descriptor_type: DescriptorType,
+    /// The number of descriptors in the binding
+    pub descriptor_count: DescriptorCount,
+    /// All stages that access the binding
+    pub stages: ShaderStages,
+    /// The name of the variable bound to the binding (if known)
+    pub name: Option<String>,
+}
+
+/// All bindings of a single descriptor set
+#[derive(Debug, Clone)]
+pub struct DescriptorSetLayout {
+    /// The index of the descriptor set
+    pub set: u32,
+    /// The bindings of the set, in ascending order
+    pub bindings: Vec<DescriptorBinding>,
+}
+
+/// A range of push constants accessed by one or more stages
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushConstantRange {
+    /// All stages that access the range
+    pub stages: ShaderStages,
+    /// The offset of the range in bytes
+    pub offset: u32,
+    /// The size of the range in bytes
+    pub size: u32,
+}
+
+/// The merged resource interface of multiple shader stages
+#[derive(Debug, Clone)]
+pub struct PipelineLayout {
+    /// All descriptor sets used by any stage, in ascending order
+    pub sets: Vec<DescriptorSetLayout>,
+    /// The push constant ranges of all stages
+    pub push_constant_ranges: Vec<PushConstantRange>,
+}
+
+/// Merges the resources of multiple [`EntryPoint`]s into a single [`PipelineLayout`].
+///
+/// The entry points may come from the same or from different [`Module`]s.
+#[derive(Debug, Default)]
+pub struct PipelineLayoutBuilder<'a> {
+    stages: Vec<(&'a Module, &'a EntryPoint)>,
+}
+
+impl<'a> PipelineLayoutBuilder<'a> {
+    /// Creates a builder without any stages
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the resources of `entry_point`, which is defined in `module`
+    #[must_use]
+    pub fn add_entry_point(mut self, module: &'a Module, entry_point: &'a EntryPoint) -> Self {
+        self.stages.push((module, entry_point));
+        self
+    }
+
+    /// Merges the resources of all added entry points.
+    ///
+    /// # Errors
+    /// - [`PipelineLayoutError::DescriptorTypeMismatch`] if two stages use the same binding with different descriptor types
+    /// - [`PipelineLayoutError::DescriptorCountMismatch`] if two stages use the same binding with different descriptor counts
+    /// - [`PipelineLayoutError::UnknownDescriptorCount`] if the descriptor count of a binding cannot be determined or does not fit into a `u32`
+    /// - [`PipelineLayoutError::BlockLayoutMismatch`] if two stages use the same buffer binding with different block layouts
+    /// - [`PipelineLayoutError::UnknownPushConstantSize`] if the size of a push constant block cannot be determined
+    pub fn build(&self) -> Result<PipelineLayout, PipelineLayoutError> {
+        // (set, binding) => (binding info, module and type id of the first declaration)
+        let mut bindings: BTreeMap<(u32, u32), (DescriptorBinding, &Module, u32)> = BTreeMap::new();
+        let mut stage_ranges: Vec<PushConstantRange> = Vec::new();
+
+        for (module, entry_point) in &self.stages {
+            let stage = ShaderStages::from(entry_point.execution_model);
+
+            for var in &entry_point.uniforms {
+                if matches!(
+                    var.descriptor_count,
+                    DescriptorCount::Unknown | DescriptorCount::Overflow
+                ) {
+                    return Err(PipelineLayoutError::UnknownDescriptorCount {
+                        set: var.set,
+                        binding: var.binding,
+                    });
+                }
+                if let Some((existing, existing_module, existing_type_id)) =
+                    bindings.get_mut(&(var.set, var.binding))
+                {
+                    if existing.descriptor_type != var.descriptor_type {
+                        return Err(PipelineLayoutError::DescriptorTypeMismatch {
+                            set: var.set,
+                            binding: var.binding,
+                            first: existing.descriptor_type,
+                            second: var.descriptor_type,
+                        });
+                    }
+                    if existing.descriptor_count != var.descriptor_count {
+                        return Err(PipelineLayoutError::DescriptorCountMismatch {
+                            set: var.set,
+                            binding: var.binding,
+                            first: existing.descriptor_count,
+                            second: var.descriptor_count,
+                        });
+                    }
+                    if matches!(
+                        var.descriptor_type,
+                        DescriptorType::UniformBuffer | DescriptorType::StorageBuffer
+                    ) && !existing_module.is_type_compatible(
+                        *existing_type_id,
+                        module,
+                        var.type_id,
+                    ) {
+                        return Err(PipelineLayoutError::BlockLayoutMismatch {
+                            set: var.set,
+                            binding: var.binding,
+                        });
+                    }
+
+                    existing.stages |= stage;
+                    if existing.name.is_none() {
+                        existing.name.clone_from(&var.name);
+                    }
+                } else {
+                    bindings.insert(
+                        (var.set, var.binding),
+                        (
+                            DescriptorBinding {
+                                binding: var.binding,
+                                descriptor_type: var.descriptor_type,
+                                descriptor_count: var.descriptor_count,
+                                stages: stage,
+                                name: var.name.clone(),
+                            },
+                            module,
+                            var.type_id,
+                        ),
+                    );
+                }
+            }
+
+            // Vulkan allows only one range per stage, so entry points of the same stage share a range covering all of them
+            if let Some((offset, size)) = Self::push_constant_range(module, entry_point)? {
+                if let Some(range) = stage_ranges.iter_mut().find(|r| r.stages == stage) {
+                    let end = (range.offset + range.size).max(offset + size);
+                    range.offset = range.offset.min(offset);
+                    range.size = end - range.offset;
+                } else {
+                    stage_ranges.push(PushConstantRange {
+                        stages: stage,
+                        offset,
+                        size,
+                    });
+                }
+            }
+        }
+
+        // stages with identical ranges share a single range
+        let mut push_constant_ranges: Vec<PushConstantRange> = Vec::new();
+        for stage_range in stage_ranges {
+            if let Some(range) = push_constant_ranges
+                .iter_mut()
+                .find(|r| r.offset == stage_range.offset && r.size == stage_range.size)
+            {
+                range.stages |= stage_range.stages;
+            } else {
+                push_constant_ranges.push(stage_range);
+            }
+        }
+
+        let mut sets: Vec<DescriptorSetLayout> = Vec::new();
+        for ((set, _), (binding, _, _)) in bindings {
+            match sets.last_mut() {
+                Some(last) if last.set == set => last.bindings.push(binding),
+                _ => sets.push(DescriptorSetLayout {
+                    set,
+                    bindings: vec![binding],
+                }),
+            }
+        }
+
+        Ok(PipelineLayout {
+            sets,
+            push_constant_ranges,
+        })
+    }
+
+    /// Calculates the byte range covered by all push constants of `entry_point`.
+    ///
+    /// Vulkan requires the offset and size of a push constant range to be multiples of 4,
+    /// so the range is widened to 4 byte boundaries (e.g. for 8-bit and 16-bit members).
+    fn push_constant_range(
+        module: &Module,
+        entry_point: &EntryPoint,
+    ) -> Result<Option<(u32, u32)>, PipelineLayoutError> {
+        let mut range: Option<(u32, u32)> = None;
+
+        for var in &entry_point.push_constants {
+            let end = module
+                .get_var_size(var)
+                .and_then(|size| size.checked_next_multiple_of(4))
+                .ok_or_else(|| PipelineLayoutError::UnknownPushConstantSize {
+                    entry_point: entry_point.name.clone(),
+                })?;
+            // stages commonly only declare the members they use, so the range starts at the first member
+            let start = match module.get_type(var.type_id) {
+                Some(Type::Struct { elements, .. }) => {
+                    elements.iter().filter_map(|e| e.offset).min().unwrap_or(0) / 4 * 4
+                }
+                _ => 0,
+            };
+
+            range = Some(match range {
+                Some((s, e)) => (s.min(start), e.max(end)),
+                None => (start, end),
+            });
+        }
+
+        Ok(range.map(|(start, end)| (start, end - start)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PipelineLayoutBuilder, PushConstantRange};
+    use crate::{test_utils::Assembler, Module, ShaderStages};
+
+    // a vertex shader with the push constant block `struct { uint16_t a; uint8_t b; }` at the given member offsets
+    fn push_constants(offsets: [u32; 2]) -> Module {
+        let mut asm = Assembler::new();
+        asm.entry_point(0, 100, &[20])
+            .op(71, &[3, 2])
+            .op(72, &[3, 0, 35, offsets[0]])
+            .op(72, &[3, 1, 35, offsets[1]])
+            .op(21, &[1, 16, 0])
+            .op(21, &[2, 8, 0])
+            .op(30, &[3, 1, 2])
+            .op(32, &[10, 9, 3])
+            .op(59, &[10, 20, 9]);
+        Module::from_words(asm.words()).unwrap()
+    }
+
+    fn push_constant_ranges(module: &Module) -> Vec<PushConstantRange> {
+        PipelineLayoutBuilder::new()
+            .add_entry_point(module, &module.get_entry_points()[0])
+            .build()
+            .unwrap()
+            .push_constant_ranges
+    }
+
+    #[test]
+    fn push_constant_range_is_aligned_to_4_bytes() {
+        assert_eq!(
+            push_constant_ranges(&push_constants([0, 2])),
+            [PushConstantRange {
+                stages: ShaderStages::VERTEX,
+                offset: 0,
+                size: 4,
+            }]
+        );
+        assert_eq!(
+            push_constant_ranges(&push_constants([6, 8])),
+            [PushConstantRange {
+                stages: ShaderStages::VERTEX,
+                offset: 4,
+                size: 8,
+            }]
+        );
+    }
+
+    #[test]
+    fn self_referencing_buffer_references_are_compatible() {
+        // `uniform Block { Node head; }` where `Node` is a `buffer_reference` to `struct { Node next; }`
+        let mut asm = Assembler::new();
+        asm.entry_point(0, 100, &[20])
+            .op(71, &[12, 2])
+            .op(71, &[20, 34, 0])
+            .op(71, &[20, 33, 0])
+            .op(72, &[11, 0, 35, 0])
+            .op(72, &[12, 0, 35, 0])
+            .op(39, &[10, 5349])
+            .op(30, &[11, 10])
+            .op(32, &[10, 5349, 11])
+            .op(30, &[12, 10])
+            .op(32, &[13, 2, 12])
+            .op(59, &[13, 20, 2]);
+        let module = Module::from_words(asm.words()).unwrap();
+        let entry_point = &module.get_entry_points()[0];
+
+        let layout = PipelineLayoutBuilder::new()
+            .add_entry_point(&module, entry_point)
+            .add_entry_point(&module, entry_point)
+            .build()
+            .unwrap();
+        assert_eq!(layout.sets[0].bindings.len(), 1);
+    }
+
+    #[test]
+    fn push_constant_ranges_are_combined_per_stage() {
+        let first = push_constants([0, 2]);
+        let second = push_constants([6, 8]);
+
+        let layout = PipelineLayoutBuilder::new()
+            .add_entry_point(&first, &first.get_entry_points()[0])
+            .add_entry_point(&second, &second.get_entry_points()[0])
+            .build()
+            .unwrap();
+        assert_eq!(
+            layout.push_constant_ranges,
+            [PushConstantRange {
+                stages: ShaderStages::VERTEX,
+                offset: 0,
+                size: 12,
+            }]
+        );
+    }
+}
@@ -0,0 +1,452 @@
+use thiserror::Error;
+
+use crate::{EntryPoint, ExecutionModel, Module, ShaderStages, StorageClass};
+
+/// The type of a [`ShaderGroup`], using the values of Vulkan's `VkRayTracingShaderGroupTypeKHR`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShaderGroupKind {
+    /// A single ray generation, miss or callable shader
+    General = 0,
+    /// A hit group for triangle geometry, without an intersection shader
+    TrianglesHitGroup = 1,
+    /// A hit group for procedural geometry, with an intersection shader
+    ProceduralHitGroup = 2,
+}
+
+/// A shader group of a ray tracing pipeline, mirroring Vulkan's `VkRayTracingShaderGroupCreateInfoKHR`.
+///
+/// All shaders are referenced by the index of their entry point in the order they were added to the [`ShaderBindingTableBuilder`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShaderGroup {
+    /// The type of the group
+    pub kind: ShaderGroupKind,
+    /// The ray generation, miss or callable shader of a general group
+    pub general: Option<usize>,
+    /// The closest hit shader of a hit group
+    pub closest_hit: Option<usize>,
+    /// The any hit shader of a hit group
+    pub any_hit: Option<usize>,
+    /// The intersection shader of a procedural hit group
+    pub intersection: Option<usize>,
+    /// All stages contained in the group
+    pub stages: ShaderStages,
+    /// The size in bytes of the largest shader record buffer used by any shader in the group
+    pub shader_record_size: u32,
+}
+
+/// All groups of one kind of shader in a shader binding table
+#[derive(Debug, Clone, Default)]
+pub struct ShaderGroupRegion {
+    /// The groups of the region, in the order they were added
+    pub groups: Vec<ShaderGroup>,
+    /// The size in bytes of each record, i.e. the handle and the largest shader record, rounded up to the handle alignment
+    pub stride: u32,
+    /// The size in bytes of all records in the region
+    pub size: u32,
+}
+
+/// The layout of the shader binding table of a ray tracing pipeline.
+///
+/// The pipeline's groups are expected in the order `raygen`, `miss`, `hit`, `callable`.
+#[derive(Debug, Clone)]
+pub struct ShaderBindingTableLayout {
+    /// All ray generation shader groups
+    pub raygen: ShaderGroupRegion,
+    /// All miss shader groups
+    pub miss: ShaderGroupRegion,
+    /// All hit groups
+    pub hit: ShaderGroupRegion,
+    /// All callable shader groups
+    pub callable: ShaderGroupRegion,
+}
+
+/// Errors that can occur while building a [`ShaderBindingTableLayout`]
+#[derive(Debug, Clone, Error)]
+pub enum ShaderBindingTableError {
+    #[error("entry point {entry_point} with execution model {execution_model:?} cannot be used in this shader group")]
+    UnexpectedExecutionModel {
+        entry_point: String,
+        execution_model: ExecutionModel,
+    },
+    #[error(
+        "the incoming ray payload of entry point {entry_point} does not match any ray payload"
+    )]
+    IncomingPayloadMismatch { entry_point: String },
+    #[error(
+        "the incoming callable data of entry point {entry_point} does not match any callable data"
+    )]
+    IncomingCallableDataMismatch { entry_point: String },
+    #[error("the hit attributes of entry point {entry_point} do not match the other shaders in its hit group")]
+    HitAttributeMismatch { entry_point: String },
+    #[error("the size of the shader record buffer in entry point {entry_point} is unknown")]
+    UnknownShaderRecordSize { entry_point: String },
+    #[error("the size of a shader binding table region does not fit into a u32")]
+    SizeOverflow,
+}
+
+/// Groups ray tracing [`EntryPoint`]s into shader groups and computes the layout of their shader binding table.
+///
+/// The entry points may come from the same or from different [`Module`]s.
+#[derive(Debug)]
+pub struct ShaderBindingTableBuilder<'a> {
+    handle_size: u32,
+    handle_alignment: u32,
+    stages: Vec<(&'a Module, &'a EntryPoint)>,
+    // indices into `stages` of (general, closest hit, any hit, intersection)
+    groups: Vec<[Option<usize>; 4]>,
+}
+
+impl<'a> ShaderBindingTableBuilder<'a> {
+    /// Creates a builder without any stages.
+    ///
+    /// `handle_size` and `handle_alignment` are the `shaderGroupHandleSize` and `shaderGroupHandleAlignment`
+    /// of the device's `VkPhysicalDeviceRayTracingPipelinePropertiesKHR`.
+    pub fn new(handle_size: u32, handle_alignment: u32) -> Self {
+        Self {
+            handle_size,
+            handle_alignment,
+            stages: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    /// Adds `entry_point`, which is defined in `module`, as its own shader group.
+    ///
+    /// Ray generation, miss and callable shaders form a general group, hit shaders a hit group containing only this shader.
+    #[must_use]
+    pub fn add_entry_point(mut self, module: &'a Module, entry_point: &'a EntryPoint) -> Self {
+        let index = self.stages.len();
+        self.stages.push((module, entry_point));
+        self.groups.push(match entry_point.execution_model {
+            ExecutionModel::ClosestHit => [None, Some(index), None, None],
+            ExecutionModel::AnyHit => [None, None, Some(index), None],
+            ExecutionModel::Intersection => [None, None, None, Some(index)],
+            _ => [Some(index), None, None, None],
+        });
+        self
+    }
+
+    /// Adds a hit group consisting of the given closest hit, any hit and intersection shaders
+    #[must_use]
+    pub fn add_hit_group(
+        mut self,
+        closest_hit: Option<(&'a Module, &'a EntryPoint)>,
+        any_hit: Option<(&'a Module, &'a EntryPoint)>,
+        intersection: Option<(&'a Module, &'a EntryPoint)>,
+    ) -> Self {
+        let mut group = [None; 4];
+        for (slot, stage) in group[1..]
+            .iter_mut()
+            .zip([closest_hit, any_hit, intersection])
+        {
+            if let Some(stage) = stage {
+                *slot = Some(self.stages.len());
+                self.stages.push(stage);
+            }
+        }
+        self.groups.push(group);
+        self
+    }
+
+    /// Groups all added entry points and computes the layout of the shader binding table.
+    ///
+    /// # Errors
+    /// - [`ShaderBindingTableError::UnexpectedExecutionModel`] if an entry point is not a ray tracing shader or was added to the wrong slot of a hit group
+    /// - [`ShaderBindingTableError::IncomingPayloadMismatch`] if an incoming ray payload matches none of the declared ray payloads
+    /// - [`ShaderBindingTableError::IncomingCallableDataMismatch`] if incoming callable data matches none of the declared callable data
+    /// - [`ShaderBindingTableError::HitAttributeMismatch`] if the shaders of a hit group declare different hit attributes
+    /// - [`ShaderBindingTableError::UnknownShaderRecordSize`] if the size of a shader record buffer cannot be determined
+    /// - [`ShaderBindingTableError::SizeOverflow`] if the stride or size of a region does not fit into a `u32`
+    pub fn build(&self) -> Result<ShaderBindingTableLayout, ShaderBindingTableError> {
+        self.check_incoming(StorageClass::RayPayload, StorageClass::IncomingRayPayload)?;
+        self.check_incoming(
+            StorageClass::CallableData,
+            StorageClass::IncomingCallableData,
+        )?;
+
+        let mut layout = ShaderBindingTableLayout {
+            raygen: ShaderGroupRegion::default(),
+            miss: ShaderGroupRegion::default(),
+            hit: ShaderGroupRegion::default(),
+            callable: ShaderGroupRegion::default(),
+        };
+
+        for [general, closest_hit, any_hit, intersection] in &self.groups {
+            let expected = [
+                ExecutionModel::ClosestHit,
+                ExecutionModel::AnyHit,
+                ExecutionModel::Intersection,
+            ];
+            for (index, model) in [closest_hit, any_hit, intersection]
+                .into_iter()
+                .zip(expected)
+            {
+                if let Some(index) = index {
+                    self.expect_execution_model(*index, &[model])?;
+                }
+            }
+
+            let (kind, region) = if let Some(index) = general {
+                let region = match self.expect_execution_model(
+                    *index,
+                    &[
+                        ExecutionModel::RayGeneration,
+                        ExecutionModel::Miss,
+                        ExecutionModel::Callable,
+                    ],
+                )? {
+                    ExecutionModel::RayGeneration => &mut layout.raygen,
+                    ExecutionModel::Miss => &mut layout.miss,
+                    _ => &mut layout.callable,
+                };
+                (ShaderGroupKind::General, region)
+            } else if intersection.is_some() {
+                (ShaderGroupKind::ProceduralHitGroup, &mut layout.hit)
+            } else {
+                (ShaderGroupKind::TrianglesHitGroup, &mut layout.hit)
+            };
+
+            let members: Vec<usize> = [general, closest_hit, any_hit, intersection]
+                .into_iter()
+                .flatten()
+                .copied()
+                .collect();
+            self.check_hit_attributes(&members)?;
+
+            let mut stages = ShaderStages::empty();
+            let mut shader_record_size = 0;
+            for index in &members {
+                let (module, entry_point) = self.stages[*index];
+                stages |= ShaderStages::from(entry_point.execution_model);
+                shader_record_size =
+                    shader_record_size.max(Self::shader_record_size(module, entry_point)?);
+            }
+
+            region.groups.push(ShaderGroup {
+                kind,
+                general: *general,
+                closest_hit: *closest_hit,
+                any_hit: *any_hit,
+                intersection: *intersection,
+                stages,
+                shader_record_size,
+            });
+        }
+
+        for region in [
+            &mut layout.raygen,
+            &mut layout.miss,
+            &mut layout.hit,
+            &mut layout.callable,
+        ] {
+            let record_size = region
+                .groups
+                .iter()
+                .map(|g| g.shader_record_size)
+                .max()
+                .unwrap_or(0);
+            let size = self
+                .handle_size
+                .checked_add(record_size)
+                .and_then(|size| size.checked_next_multiple_of(self.handle_alignment.max(1)))
+                .and_then(|stride| {
+                    let size = stride.checked_mul(u32::try_from(region.groups.len()).ok()?)?;
+                    Some((stride, size))
+                });
+            (region.stride, region.size) = size.ok_or(ShaderBindingTableError::SizeOverflow)?;
+        }
+
+        Ok(layout)
+    }
+
+    /// Returns the execution model of the stage `index` if it is one of `expected`
+    fn expect_execution_model(
+        &self,
+        index: usize,
+        expected: &[ExecutionModel],
+    ) -> Result<ExecutionModel, ShaderBindingTableError> {
+        let (_, entry_point) = self.stages[index];
+        if expected.contains(&entry_point.execution_model) {
+            Ok(entry_point.execution_model)
+        } else {
+            Err(ShaderBindingTableError::UnexpectedExecutionModel {
+                entry_point: entry_point.name.clone(),
+                execution_model: entry_point.execution_model,
+            })
+        }
+    }
+
+    /// Checks that every `incoming` variable matches the type of one of the `outgoing` variables.
+    ///
+    /// The locations of outgoing variables are local to the shader tracing the ray or executing the callable shader,
+    /// so different stages may declare different types at the same location.
+    fn check_incoming(
+        &self,
+        outgoing: StorageClass,
+        incoming: StorageClass,
+    ) -> Result<(), ShaderBindingTableError> {
+        let declared: Vec<(&Module, u32)> = self
+            .stages
+            .iter()
+            .flat_map(|(module, entry_point)| {
+                entry_point
+                    .ray_tracing_variables
+                    .iter()
+                    .filter(|var| var.storage_class == outgoing)
+                    .map(|var| (*module, var.type_id))
+            })
+            .collect();
+
+        // the caller of a shader may not be part of this pipeline, so incoming variables are only checked against known declarations
+        if declared.is_empty() {
+            return Ok(());
+        }
+        for (module, entry_point) in &self.stages {
+            for var in &entry_point.ray_tracing_variables {
+                if var.storage_class == incoming
+                    && !declared.iter().any(|(other_module, type_id)| {
+                        other_module.is_type_compatible(*type_id, module, var.type_id)
+                    })
+                {
+                    let entry_point = entry_point.name.clone();
+                    return Err(if outgoing == StorageClass::RayPayload {
+                        ShaderBindingTableError::IncomingPayloadMismatch { entry_point }
+                    } else {
+                        ShaderBindingTableError::IncomingCallableDataMismatch { entry_point }
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that all stages of a group that declare hit attributes use compatible types
+    fn check_hit_attributes(&self, members: &[usize]) -> Result<(), ShaderBindingTableError> {
+        let mut first: Option<(&Module, u32)> = None;
+
+        for index in members {
+            let (module, entry_point) = self.stages[*index];
+            for var in &entry_point.ray_tracing_variables {
+                if var.storage_class != StorageClass::HitAttribute {
+                    continue;
+                }
+                match first {
+                    Some((first_module, type_id)) => {
+                        if !first_module.is_type_compatible(type_id, module, var.type_id) {
+                            return Err(ShaderBindingTableError::HitAttributeMismatch {
+                                entry_point: entry_point.name.clone(),
+                            });
+                        }
+                    }
+                    None => first = Some((module, var.type_id)),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Calculates the size of the shader record buffer used by `entry_point`, 0 if it does not use one
+    fn shader_record_size(
+        module: &Module,
+        entry_point: &EntryPoint,
+    ) -> Result<u32, ShaderBindingTableError> {
+        if !entry_point
+            .ray_tracing_variables
+            .iter()
+            .any(|v| v.storage_class == StorageClass::ShaderRecordBuffer)
+        {
+            return Ok(0);
+        }
+
+        module
+            .shader_record_layout(entry_point)
+            .map(|layout| layout.size)
+            .ok_or_else(|| ShaderBindingTableError::UnknownShaderRecordSize {
+                entry_point: entry_point.name.clone(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ShaderBindingTableBuilder, ShaderBindingTableError};
+    use crate::{test_utils::Assembler, Module};
+
+    const FLOAT: u32 = 1;
+    const VEC4: u32 = 2;
+    const INT: u32 = 3;
+    const RAY_PAYLOAD: u32 = 5338;
+    const INCOMING_RAY_PAYLOAD: u32 = 5342;
+
+    // a ray tracing shader with the execution model `model`, declaring the variables `(storage class, type id)` at location 0
+    fn ray_shader(model: u32, vars: &[(u32, u32)]) -> Module {
+        let ids: Vec<u32> = (20..).take(vars.len()).collect();
+        let mut asm = Assembler::new();
+        asm.entry_point(model, 100, &ids)
+            .op(22, &[FLOAT, 32])
+            .op(23, &[VEC4, FLOAT, 4])
+            .op(21, &[INT, 32, 1]);
+        for (id, (storage_class, type_id)) in ids.iter().zip(vars) {
+            asm.op(71, &[*id, 30, 0])
+                .op(32, &[id - 10, *storage_class, *type_id])
+                .op(59, &[id - 10, *id, *storage_class]);
+        }
+        Module::from_words(asm.words()).unwrap()
+    }
+
+    #[test]
+    fn payload_locations_are_local_to_each_shader() {
+        // the ray generation shader traces a primary ray, the closest hit shader a shadow ray, both at location 0
+        let raygen = ray_shader(5313, &[(RAY_PAYLOAD, VEC4)]);
+        let closest_hit = ray_shader(5316, &[(INCOMING_RAY_PAYLOAD, VEC4), (RAY_PAYLOAD, FLOAT)]);
+        let miss = ray_shader(5317, &[(INCOMING_RAY_PAYLOAD, FLOAT)]);
+
+        let layout = ShaderBindingTableBuilder::new(32, 32)
+            .add_entry_point(&raygen, &raygen.get_entry_points()[0])
+            .add_entry_point(&closest_hit, &closest_hit.get_entry_points()[0])
+            .add_entry_point(&miss, &miss.get_entry_points()[0])
+            .build()
+            .unwrap();
+        assert_eq!(layout.hit.groups.len(), 1);
+    }
+
+    #[test]
+    fn unmatched_incoming_payload_is_rejected() {
+        let raygen = ray_shader(5313, &[(RAY_PAYLOAD, VEC4)]);
+        let miss = ray_shader(5317, &[(INCOMING_RAY_PAYLOAD, INT)]);
+
+        let result = ShaderBindingTableBuilder::new(32, 32)
+            .add_entry_point(&raygen, &raygen.get_entry_points()[0])
+            .add_entry_point(&miss, &miss.get_entry_points()[0])
+            .build();
+        assert!(matches!(
+            result,
+            Err(ShaderBindingTableError::IncomingPayloadMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn overflowing_region_size_is_rejected() {
+        // a ray generation shader with the shader record `{ float a[0x3fffffff]; }`
+        let mut asm = Assembler::new();
+        asm.entry_point(5313, 100, &[20])
+            .op(71, &[9, 6, 4])
+            .op(71, &[10, 2])
+            .op(72, &[10, 0, 35, 0])
+            .op(22, &[FLOAT, 32])
+            .op(21, &[INT, 32, 1])
+            .op(43, &[INT, 8, 0x3fff_ffff])
+            .op(28, &[9, FLOAT, 8])
+            .op(30, &[10, 9])
+            .op(32, &[11, 5343, 10])
+            .op(59, &[11, 20, 5343]);
+        let raygen = Module::from_words(asm.words()).unwrap();
+
+        let result = ShaderBindingTableBuilder::new(32, 32)
+            .add_entry_point(&raygen, &raygen.get_entry_points()[0])
+            .build();
+        assert!(matches!(result, Err(ShaderBindingTableError::SizeOverflow)));
+    }
+}
@@ -0,0 +1,51 @@
+/// Assembles SPIR-V modules word by word for the unit tests
+pub(crate) struct Assembler {
+    words: Vec<u32>,
+}
+
+impl Assembler {
+    /// Starts a SPIR-V 1.5 module with an id bound of 1000
+    pub(crate) fn new() -> Self {
+        Self {
+            words: vec![0x07230203, 0x00010500, 0, 1000, 0],
+        }
+    }
+
+    /// Appends the instruction `opcode` with the operands `args`
+    pub(crate) fn op(&mut self, opcode: u16, args: &[u32]) -> &mut Self {
+        let length = u32::try_from(args.len() + 1).unwrap();
+        self.words.push((length << 16) | u32::from(opcode));
+        self.words.extend_from_slice(args);
+        self
+    }
+
+    /// Appends the instruction `opcode` with the operands `pre`, the nul-terminated string `string` and the operands `post`
+    pub(crate) fn op_str(
+        &mut self,
+        opcode: u16,
+        pre: &[u32],
+        string: &str,
+        post: &[u32],
+    ) -> &mut Self {
+        let mut bytes = string.as_bytes().to_vec();
+        bytes.resize((bytes.len() / 4 + 1) * 4, 0);
+
+        let mut args = pre.to_vec();
+        args.extend(
+            bytes
+                .chunks(4)
+                .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]])),
+        );
+        args.extend_from_slice(post);
+        self.op(opcode, &args)
+    }
+
+    /// Appends `OpEntryPoint` for the execution model `model`, the function `func` and the interface variables `interface`
+    pub(crate) fn entry_point(&mut self, model: u32, func: u32, interface: &[u32]) -> &mut Self {
+        self.op_str(15, &[model, func], "main", interface)
+    }
+
+    pub(crate) fn words(&self) -> &[u32] {
+        &self.words
+    }
+}
@@ -0,0 +1,311 @@
+use crate::{ArrayLength, EntryPoint, Module, Type};
+
+/// Selects how [`Module::vertex_input_layout`] distributes the vertex attributes over vertex buffer bindings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VertexBindingPolicy {
+    /// All attributes are interleaved in a single binding 0, ordered by location
+    Interleaved,
+    /// Each input variable gets its own binding, numbered in the order of their locations.
+    ///
+    /// The attributes of matrix and array inputs are interleaved within their binding.
+    PerAttribute,
+}
+
+/// A single vertex attribute, mirroring Vulkan's `VkVertexInputAttributeDescription`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VertexAttribute {
+    /// The shader input location of the attribute
+    pub location: u32,
+    /// The vertex buffer binding the attribute is read from
+    pub binding: u32,
+    /// The Vulkan `VkFormat` value of the attribute (e.g. 106 = `VK_FORMAT_R32G32B32_SFLOAT`)
+    pub format: u32,
+    /// The offset of the attribute in bytes, relative to the start of a vertex in its binding
+    pub offset: u32,
+    /// The name of the input variable the attribute belongs to (if known)
+    pub name: Option<String>,
+}
+
+/// A single vertex buffer binding, mirroring Vulkan's `VkVertexInputBindingDescription`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VertexBinding {
+    /// The binding number
+    pub binding: u32,
+    /// The distance in bytes between two consecutive vertices
+    pub stride: u32,
+}
+
+/// The vertex input state of a vertex shader, as returned by [`Module::vertex_input_layout`]
+#[derive(Debug, Clone)]
+pub struct VertexInputLayout {
+    /// All vertex buffer bindings, in ascending order
+    pub bindings: Vec<VertexBinding>,
+    /// All vertex attributes, in ascending order of their locations
+    pub attributes: Vec<VertexAttribute>,
+}
+
+/// A single attribute of an input variable, before it is placed in a binding
+struct RawAttribute {
+    /// The location relative to the location of the variable
+    location: u32,
+    format: u32,
+    size: u32,
+    alignment: u32,
+}
+
+impl Module {
+    /// Generates the vertex attributes and bindings needed to feed the inputs of the vertex shader `entry_point`.
+    ///
+    /// Matrices are expanded into one attribute per column and arrays into one attribute per element.
+    /// 64-bit vectors with 3 or 4 components use a single attribute, but take two locations.
+    /// Returns [`None`] if the type of any input cannot be used as a vertex attribute or any location, offset or stride
+    /// does not fit into a `u32`.
+    pub fn vertex_input_layout(
+        &self,
+        entry_point: &EntryPoint,
+        policy: VertexBindingPolicy,
+    ) -> Option<VertexInputLayout> {
+        let mut inputs: Vec<_> = entry_point.inputs.iter().collect();
+        inputs.sort_by_key(|input| input.location);
+
+        let mut bindings: Vec<VertexBinding> = Vec::new();
+        let mut attributes = Vec::new();
+        // the largest alignment of any attribute in each binding
+        let mut alignments: Vec<u32> = Vec::new();
+
+        for input in inputs {
+            let binding = match policy {
+                VertexBindingPolicy::Interleaved => 0,
+                VertexBindingPolicy::PerAttribute => u32::try_from(bindings.len()).ok()?,
+            };
+            if bindings.last().is_none_or(|b| b.binding != binding) {
+                bindings.push(VertexBinding { binding, stride: 0 });
+                alignments.push(1);
+            }
+            let binding_layout = bindings.last_mut()?;
+            let alignment = alignments.last_mut()?;
+
+            // all locations of the input have to be addressable before it is expanded
+            input
+                .location
+                .checked_add(self.location_count(input.type_id)?)?;
+
+            let mut raw_attributes = Vec::new();
+            self.collect_vertex_attributes(input.type_id, 0, &mut raw_attributes)?;
+
+            for attribute in raw_attributes {
+                let offset = binding_layout
+                    .stride
+                    .checked_next_multiple_of(attribute.alignment)?;
+                binding_layout.stride = offset.checked_add(attribute.size)?;
+                *alignment = (*alignment).max(attribute.alignment);
+
+                attributes.push(VertexAttribute {
+                    location: input.location.checked_add(attribute.location)?,
+                    binding,
+                    format: attribute.format,
+                    offset,
+                    name: input.name.clone(),
+                });
+            }
+        }
+
+        // consecutive vertices need to keep the alignment of their attributes
+        for (binding, alignment) in bindings.iter_mut().zip(alignments) {
+            binding.stride = binding.stride.checked_next_multiple_of(alignment)?;
+        }
+
+        Some(VertexInputLayout {
+            bindings,
+            attributes,
+        })
+    }
+
+    // Expands the input type `type_id` into its attributes, starting at the relative location `location`.
+    // Returns the number of locations taken by the type.
+    fn collect_vertex_attributes(
+        &self,
+        type_id: u32,
+        location: u32,
+        attributes: &mut Vec<RawAttribute>,
+    ) -> Option<u32> {
+        let (component_type_id, count) = match self.get_type(type_id)? {
+            Type::Int { .. } | Type::Float { .. } => (type_id, 1),
+            Type::Vector {
+                component_type_id,
+                count,
+            } => (*component_type_id, *count),
+            Type::Matrix {
+                column_type_id,
+                columns,
+            } => return self.collect_vertex_array(*column_type_id, *columns, location, attributes),
+            Type::Array {
+                element_type_id,
+                length,
+                ..
+            } => {
+                let length = match length {
+                    ArrayLength::Runtime => return None,
+                    length => length.value()?,
+                };
+                return self.collect_vertex_array(*element_type_id, length, location, attributes);
+            }
+            _ => return None,
+        };
+
+        let (width, format) = match self.get_type(component_type_id)? {
+            Type::Int { width, signed } => {
+                (*width, Self::vertex_format(*width, Some(*signed), count)?)
+            }
+            Type::Float { width } => (*width, Self::vertex_format(*width, None, count)?),
+            _ => return None,
+        };
+        let component_size = width / 8;
+
+        attributes.push(RawAttribute {
+            location,
+            format,
+            size: component_size * count,
+            alignment: component_size,
+        });
+
+        self.location_count(type_id)
+    }
+
+    fn collect_vertex_array(
+        &self,
+        element_type_id: u32,
+        length: u32,
+        location: u32,
+        attributes: &mut Vec<RawAttribute>,
+    ) -> Option<u32> {
+        let mut locations = 0;
+        for _ in 0..length {
+            let element_location = location.checked_add(locations)?;
+            locations = locations.checked_add(self.collect_vertex_attributes(
+                element_type_id,
+                element_location,
+                attributes,
+            )?)?;
+        }
+        Some(locations)
+    }
+
+    /// Returns the `VkFormat` of a vertex attribute with `count` components of `width` bits.
+    ///
+    /// `signed` is [`None`] for floats.
+    fn vertex_format(width: u32, signed: Option<bool>, count: u32) -> Option<u32> {
+        // the formats with 1 to 4 components
+        let formats = match (width, signed) {
+            (8, Some(false)) => [13, 20, 27, 41],
+            (8, Some(true)) => [14, 21, 28, 42],
+            (16, Some(false)) => [74, 81, 88, 95],
+            (16, Some(true)) => [75, 82, 89, 96],
+            (16, None) => [76, 83, 90, 97],
+            (32, Some(false)) => [98, 101, 104, 107],
+            (32, Some(true)) => [99, 102, 105, 108],
+            (32, None) => [100, 103, 106, 109],
+            (64, Some(false)) => [110, 113, 116, 119],
+            (64, Some(true)) => [111, 114, 117, 120],
+            (64, None) => [112, 115, 118, 121],
+            _ => return None,
+        };
+
+        formats
+            .get(usize::try_from(count).ok()?.checked_sub(1)?)
+            .copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{VertexAttribute, VertexBinding, VertexBindingPolicy};
+    use crate::{test_utils::Assembler, Module};
+
+    // a vertex shader with the inputs `uvec4` at location 0, `mat4` at location 1 and `dvec3[2]` at location 5
+    fn vertex_shader() -> Module {
+        let mut asm = Assembler::new();
+        asm.entry_point(0, 100, &[20, 21, 22])
+            .op(71, &[20, 30, 0])
+            .op(71, &[21, 30, 1])
+            .op(71, &[22, 30, 5])
+            .op(21, &[1, 32, 0])
+            .op(23, &[2, 1, 4])
+            .op(22, &[3, 32])
+            .op(23, &[4, 3, 4])
+            .op(24, &[5, 4, 4])
+            .op(22, &[6, 64])
+            .op(23, &[7, 6, 3])
+            .op(43, &[1, 8, 2])
+            .op(28, &[9, 7, 8])
+            .op(32, &[10, 1, 2])
+            .op(32, &[11, 1, 5])
+            .op(32, &[12, 1, 9])
+            .op(59, &[10, 20, 1])
+            .op(59, &[11, 21, 1])
+            .op(59, &[12, 22, 1]);
+        Module::from_words(asm.words()).unwrap()
+    }
+
+    // (location, binding, format, offset) of an attribute
+    type Attribute = (u32, u32, u32, u32);
+
+    // the attributes and the (binding, stride) of every binding
+    fn layout(policy: VertexBindingPolicy) -> (Vec<Attribute>, Vec<(u32, u32)>) {
+        let module = vertex_shader();
+        let layout = module
+            .vertex_input_layout(&module.get_entry_points()[0], policy)
+            .unwrap();
+        (
+            layout
+                .attributes
+                .iter()
+                .map(|a: &VertexAttribute| (a.location, a.binding, a.format, a.offset))
+                .collect(),
+            layout
+                .bindings
+                .iter()
+                .map(|b: &VertexBinding| (b.binding, b.stride))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn interleaved_attributes() {
+        // R32G32B32A32_UINT = 107, R32G32B32A32_SFLOAT = 109, R64G64B64_SFLOAT = 118
+        assert_eq!(
+            layout(VertexBindingPolicy::Interleaved),
+            (
+                vec![
+                    (0, 0, 107, 0),
+                    (1, 0, 109, 16),
+                    (2, 0, 109, 32),
+                    (3, 0, 109, 48),
+                    (4, 0, 109, 64),
+                    (5, 0, 118, 80),
+                    (7, 0, 118, 104),
+                ],
+                vec![(0, 128)]
+            )
+        );
+    }
+
+    #[test]
+    fn per_attribute_bindings() {
+        assert_eq!(
+            layout(VertexBindingPolicy::PerAttribute),
+            (
+                vec![
+                    (0, 0, 107, 0),
+                    (1, 1, 109, 0),
+                    (2, 1, 109, 16),
+                    (3, 1, 109, 32),
+                    (4, 1, 109, 48),
+                    (5, 2, 118, 0),
+                    (7, 2, 118, 24),
+                ],
+                vec![(0, 16), (1, 64), (2, 48)]
+            )
+        );
+    }
+}